@@ -0,0 +1,64 @@
+use bank_statement_rs::{Parser, ParserBuilder, QfxParser};
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+const TRANSACTIONS: usize = 200;
+
+fn sgml_statement() -> String {
+    let mut content = String::from(
+        "OFXHEADER:100\nDATA:OFXSGML\nVERSION:102\n\n<OFX>\n<BANKMSGSRSV1>\n<STMTTRNRS>\n<TRNUID>1\n<STMTRS>\n<CURDEF>USD\n<BANKTRANLIST>\n",
+    );
+    for i in 0..TRANSACTIONS {
+        content.push_str(&format!(
+            "<STMTTRN>\n<TRNTYPE>DEBIT\n<DTPOSTED>202512{:02}120000\n<TRNAMT>-{}.{:02}\n<FITID>{}\n<NAME>Merchant {}\n<MEMO>Purchase\n</STMTTRN>\n",
+            i % 28 + 1,
+            i + 1,
+            i % 100,
+            i,
+            i % 17
+        ));
+    }
+    content.push_str("</BANKTRANLIST>\n</STMTRS>\n</STMTTRNRS>\n</BANKMSGSRSV1>\n</OFX>\n");
+    content
+}
+
+fn xml_statement() -> String {
+    let mut content = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<OFX><BANKMSGSRSV1><STMTTRNRS><STMTRS><CURDEF>USD</CURDEF><BANKTRANLIST>\n",
+    );
+    for i in 0..TRANSACTIONS {
+        content.push_str(&format!(
+            "<STMTTRN><TRNTYPE>DEBIT</TRNTYPE><DTPOSTED>202512{:02}120000</DTPOSTED><TRNAMT>-{}.{:02}</TRNAMT><FITID>{}</FITID><NAME>Merchant {}</NAME><MEMO>Purchase</MEMO></STMTTRN>\n",
+            i % 28 + 1,
+            i + 1,
+            i % 100,
+            i,
+            i % 17
+        ));
+    }
+    content.push_str("</BANKTRANLIST></STMTRS></STMTTRNRS></BANKMSGSRSV1></OFX>\n");
+    content
+}
+
+fn bench_qfx(c: &mut Criterion) {
+    let sgml = sgml_statement();
+    let xml = xml_statement();
+
+    c.bench_function("qfx_sgml_parser", |b| {
+        b.iter(|| QfxParser::parse(black_box(&sgml)).unwrap())
+    });
+    c.bench_function("qfx_xml_parser", |b| {
+        b.iter(|| QfxParser::parse(black_box(&xml)).unwrap())
+    });
+    c.bench_function("qfx_sgml_builder", |b| {
+        b.iter(|| {
+            ParserBuilder::new()
+                .content(black_box(&sgml))
+                .parse()
+                .unwrap()
+        })
+    });
+}
+
+criterion_group!(benches, bench_qfx);
+criterion_main!(benches);
@@ -0,0 +1,89 @@
+use bank_statement_rs::{DynParser, ParserBuilder, Transaction};
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use std::{env, str::FromStr};
+
+const SAMPLE_TSV: &str = "date\tamount\tpayee
+2025-12-26\t-50.00\tCoffee Shop
+2025-12-27\t1500.00\tPayroll
+2025-12-28\t-12.34\tBakery
+";
+
+/// Tab-separated export with a `date`, `amount` and `payee` header.
+struct TsvParser;
+
+impl DynParser for TsvParser {
+    fn parse(&self, content: &str) -> Result<Vec<Transaction>, String> {
+        content
+            .lines()
+            .skip(1)
+            .filter(|line| !line.trim().is_empty())
+            .enumerate()
+            .map(|(row, line)| {
+                let fields: Vec<&str> = line.split('\t').collect();
+                let [date, amount, payee] = fields[..] else {
+                    return Err(format!("Row {}: expected 3 columns", row + 1));
+                };
+                let amount = Decimal::from_str(amount).map_err(|e| e.to_string())?;
+                Ok(Transaction {
+                    date: NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|e| e.to_string())?,
+                    user_date: None,
+                    amount,
+                    payee: Some(payee.to_string()),
+                    normalized_payee: None,
+                    transaction_type: if amount.is_sign_negative() {
+                        "DEBIT".to_string()
+                    } else {
+                        "CREDIT".to_string()
+                    },
+                    fitid: None,
+                    check_number: None,
+                    status: None,
+                    memo: None,
+                    account_id: None,
+                    currency: None,
+                    original_currency: None,
+                    original_amount: None,
+                    raw_source: None,
+                })
+            })
+            .collect()
+    }
+
+    fn is_supported(&self, filename: Option<&str>, content: &str) -> bool {
+        filename.is_some_and(|name| name.to_lowercase().ends_with(".tsv"))
+            || content.starts_with("date\tamount\tpayee")
+    }
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = env::args().collect();
+
+    let content = if args.len() > 1 {
+        std::fs::read_to_string(&args[1])?
+    } else {
+        println!("Using built-in example TSV data\n");
+        SAMPLE_TSV.to_string()
+    };
+
+    let transactions = ParserBuilder::new()
+        .content(&content)
+        .with_parser(Box::new(TsvParser))
+        .normalize_payees(true)
+        .parse()?;
+
+    println!("Found {} transactions\n", transactions.len());
+
+    for (i, tx) in transactions.iter().enumerate() {
+        println!("Transaction {}:", i + 1);
+        println!("  Date: {}", tx.date);
+        println!("  Type: {}", tx.transaction_type);
+        println!("  Amount: {}", tx.amount);
+        if let Some(payee) = &tx.normalized_payee {
+            println!("  Payee: {}", payee);
+        }
+        println!();
+    }
+
+    Ok(())
+}
@@ -30,6 +30,27 @@ impl TryFrom<ParsedTransaction> for MyTransaction {
                     category: category.to_string(),
                 })
             }
+            ParsedTransaction::Camt(camt) => {
+                let category = if camt.is_debit() { "Expense" } else { "Income" };
+
+                Ok(MyTransaction {
+                    // Booking dates are `YYYY-MM-DD` or an ISO date-time.
+                    date: camt
+                        .booking_date
+                        .get(..10)
+                        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+                        .ok_or(StatementParseError::CamtDateInvalidFormat)?,
+                    amount: camt.amount.to_string().parse().unwrap_or(0.0),
+                    merchant: camt.description.unwrap_or_else(|| "Unknown".to_string()),
+                    category: category.to_string(),
+                })
+            }
+            ParsedTransaction::Custom(transaction) => Ok(MyTransaction {
+                date: transaction.date,
+                amount: transaction.amount.to_string().parse().unwrap_or(0.0),
+                merchant: transaction.payee.unwrap_or_else(|| "Unknown".to_string()),
+                category: "Other".to_string(),
+            }),
         }
     }
 }
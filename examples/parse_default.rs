@@ -1,19 +1,58 @@
-use bank_statement_rs::ParserBuilder;
+use bank_statement_rs::{FileFormat, NumberLocale, ParserBuilder, errors::StatementParseError};
+use chrono::NaiveDate;
 use std::env;
 
+const USAGE: &str = "usage: parse_default [--format qfx|ofx] [--locale us|uk|br|de|fr|ch] \
+                     [--date-format <chrono format>] [file]";
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let args: Vec<String> = env::args().collect();
+    let mut builder = ParserBuilder::new();
+    let mut file_path = None;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                let ext = args.next().ok_or(USAGE)?.to_lowercase();
+                let format = FileFormat::all()
+                    .iter()
+                    .find(|format| format.extensions().contains(&ext.as_str()))
+                    .ok_or(USAGE)?;
+                builder = builder.format(*format);
+            }
+            "--locale" => {
+                let locale = match args.next().ok_or(USAGE)?.as_str() {
+                    "us" => NumberLocale::UsEnglish,
+                    "uk" => NumberLocale::UkEnglish,
+                    "br" => NumberLocale::BrazilianPortuguese,
+                    "de" => NumberLocale::GermanGerman,
+                    "fr" => NumberLocale::FrenchFrench,
+                    "ch" => NumberLocale::SwissGerman,
+                    _ => return Err(USAGE.into()),
+                };
+                builder = builder.number_locale(locale);
+            }
+            "--date-format" => {
+                let date_format = args.next().ok_or(USAGE)?;
+                builder = builder.date_parser(move |raw| {
+                    NaiveDate::parse_and_remainder(raw, &date_format)
+                        .map(|(date, _)| date)
+                        .map_err(|_| StatementParseError::QfxDateInvalidFormat)
+                });
+            }
+            _ if arg.starts_with("--") => return Err(USAGE.into()),
+            _ => file_path = Some(arg),
+        }
+    }
 
-    let file_path = if args.len() > 1 {
-        &args[1]
-    } else {
+    let file_path = file_path.unwrap_or_else(|| {
         println!("Using example QFX data from examples/sample.qfx\n");
-        "examples/sample.qfx"
-    };
+        "examples/sample.qfx".to_string()
+    });
 
     let content = std::fs::read_to_string(file_path)?;
 
-    let transactions = ParserBuilder::new().content(&content).parse()?;
+    let transactions = builder.content(&content).parse()?;
 
     println!("Found {} transactions\n", transactions.len());
 
@@ -10,12 +10,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         let file_path = &args[1];
         println!("Parsing QFX file: {}\n", file_path);
 
-        let content = std::fs::read_to_string(file_path)?;
-
-        let transactions = ParserBuilder::new()
-            .content(&content)
-            .filename(file_path)
-            .parse()?;
+        let transactions = ParserBuilder::from_path(file_path).parse()?;
 
         println!("Found {} transactions\n", transactions.len());
 
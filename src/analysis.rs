@@ -0,0 +1,609 @@
+use crate::{Transaction, TransactionType};
+use chrono::{Datelike, Days, NaiveDate};
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+
+pub const EXACT: Decimal = Decimal::ZERO;
+
+/// True when `a` and `b` differ by at most `epsilon` (inclusive).
+///
+/// Comparison is numeric, so `50.0` equals `50.00` even with [`EXACT`]. A
+/// negative epsilon is treated as its absolute value.
+pub fn amounts_equal(a: Decimal, b: Decimal, epsilon: Decimal) -> bool {
+    (a - b).abs() <= epsilon.abs()
+}
+
+const PAYEE_SUFFIXES: &[&str] = &["INC", "LLC", "LTD", "CO", "CORP"];
+
+/// Card processor markers banks put in front of the merchant name.
+const PAYEE_PREFIXES: &[&str] = &["SQ *", "TST*", "PAYPAL *"];
+
+/// Normalizes a payee for matching recurring merchants.
+///
+/// Steps, in order:
+/// 1. Uppercase.
+/// 2. Strip one leading card processor prefix (`SQ *`, `TST*`, `PAYPAL *`).
+/// 3. Split on whitespace and commas, collapsing runs.
+/// 4. Drop store-number tokens of the form `#1234`.
+/// 5. Repeatedly drop trailing tokens that are all digits or a common
+///    company suffix (`INC`, `LLC`, `LTD`, `CO`, `CORP`, ignoring a trailing
+///    `.`).
+/// 6. Join the remaining tokens with single spaces.
+pub fn normalize_payee(payee: &str) -> String {
+    let upper = payee.to_uppercase();
+    let upper = upper.trim_start();
+    let unprefixed = PAYEE_PREFIXES
+        .iter()
+        .find_map(|prefix| upper.strip_prefix(prefix))
+        .unwrap_or(upper);
+    let mut tokens: Vec<&str> = unprefixed
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|token| !token.is_empty() && !is_store_number(token))
+        .collect();
+
+    while let Some(last) = tokens.last() {
+        let bare = last.trim_end_matches('.');
+        if bare.chars().all(|c| c.is_ascii_digit()) || PAYEE_SUFFIXES.contains(&bare) {
+            tokens.pop();
+        } else {
+            break;
+        }
+    }
+
+    tokens.join(" ")
+}
+
+/// Sums amounts per currency so EUR and USD never end up in one total.
+///
+/// Transactions without a currency are grouped under `default_currency`,
+/// e.g. `"UNKNOWN"`.
+pub fn net_by_currency(
+    transactions: &[Transaction],
+    default_currency: &str,
+) -> BTreeMap<String, Decimal> {
+    let mut totals = BTreeMap::new();
+    for transaction in transactions {
+        let currency = transaction.currency.as_deref().unwrap_or(default_currency);
+        *totals.entry(currency.to_string()).or_insert(Decimal::ZERO) += transaction.amount;
+    }
+    totals
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Granularity {
+    Daily,
+    /// ISO weeks, starting on Monday.
+    Weekly,
+    Monthly,
+    Quarterly,
+}
+
+impl Granularity {
+    /// First day of the period containing `date`.
+    pub fn period_start(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Granularity::Daily => date,
+            Granularity::Weekly => date - Days::new(date.weekday().num_days_from_monday().into()),
+            Granularity::Monthly => date.with_day(1).unwrap(),
+            Granularity::Quarterly => {
+                NaiveDate::from_ymd_opt(date.year(), (date.month0() / 3) * 3 + 1, 1).unwrap()
+            }
+        }
+    }
+}
+
+/// Sums amounts into buckets keyed by each period's first day.
+///
+/// Periods without transactions are absent rather than zero.
+pub fn bucket_by(
+    transactions: &[Transaction],
+    granularity: Granularity,
+) -> BTreeMap<NaiveDate, Decimal> {
+    let mut buckets = BTreeMap::new();
+    for transaction in transactions {
+        *buckets
+            .entry(granularity.period_start(transaction.date))
+            .or_insert(Decimal::ZERO) += transaction.amount;
+    }
+    buckets
+}
+
+/// How many transactions have a payee. `memo_only` counts those without
+/// one whose memo could stand in for it; `missing` those with neither.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PayeeCounts {
+    pub present: usize,
+    pub memo_only: usize,
+    pub missing: usize,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FitidCounts {
+    pub present: usize,
+    pub missing: usize,
+}
+
+/// How informative `transaction_type` is. `generic` counts codes that say
+/// little about the transaction (`OTHER`, or anything outside the OFX
+/// list such as `WIRE`), as sent by the bank; `missing` counts blank ones.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TypeCounts {
+    pub specific: usize,
+    pub generic: usize,
+    pub missing: usize,
+}
+
+/// Outcome of [`field_completeness`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FieldCompleteness {
+    pub payee: PayeeCounts,
+    pub fitid: FitidCounts,
+    pub transaction_type: TypeCounts,
+}
+
+/// Counts how often `payee`, `fitid` and `transaction_type` are usable,
+/// for flagging low-quality feeds.
+///
+/// This only looks at the output: conversion never fills these fields in
+/// from elsewhere, so every count reflects what the file contained.
+pub fn field_completeness(transactions: &[Transaction]) -> FieldCompleteness {
+    let mut completeness = FieldCompleteness::default();
+    for transaction in transactions {
+        let payee = &mut completeness.payee;
+        match (&transaction.payee, &transaction.memo) {
+            (Some(_), _) => payee.present += 1,
+            (None, Some(_)) => payee.memo_only += 1,
+            (None, None) => payee.missing += 1,
+        }
+
+        if transaction.fitid.is_some() {
+            completeness.fitid.present += 1;
+        } else {
+            completeness.fitid.missing += 1;
+        }
+
+        let transaction_type = &mut completeness.transaction_type;
+        if transaction.transaction_type.trim().is_empty() {
+            transaction_type.missing += 1;
+        } else if matches!(transaction.kind(), TransactionType::Other(_)) {
+            transaction_type.generic += 1;
+        } else {
+            transaction_type.specific += 1;
+        }
+    }
+    completeness
+}
+
+/// Totals over a set of transactions, built with
+/// `TransactionSummary::from(transactions.as_slice())`.
+///
+/// Positive amounts count as credits and negative ones as debits, so
+/// `total_debits` is negative (or zero) and `net` is their sum. An empty
+/// slice gives zero totals and no `date_range`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TransactionSummary {
+    pub total_credits: Decimal,
+    pub total_debits: Decimal,
+    pub net: Decimal,
+    pub count: usize,
+    /// Earliest and latest posting date.
+    pub date_range: Option<(NaiveDate, NaiveDate)>,
+}
+
+impl From<&[Transaction]> for TransactionSummary {
+    fn from(transactions: &[Transaction]) -> Self {
+        let mut summary = TransactionSummary {
+            count: transactions.len(),
+            ..Default::default()
+        };
+        for transaction in transactions {
+            if transaction.amount.is_sign_negative() {
+                summary.total_debits += transaction.amount;
+            } else {
+                summary.total_credits += transaction.amount;
+            }
+            summary.date_range = Some(match summary.date_range {
+                Some((first, last)) => (first.min(transaction.date), last.max(transaction.date)),
+                None => (transaction.date, transaction.date),
+            });
+        }
+        summary.net = summary.total_credits + summary.total_debits;
+        summary
+    }
+}
+
+/// Tolerances for [`reconcile`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReconcileOptions {
+    /// How many days apart two transactions may be posted and still match
+    /// on amount and date.
+    pub date_window_days: u64,
+    /// Passed to [`amounts_equal`].
+    pub amount_epsilon: Decimal,
+}
+
+impl Default for ReconcileOptions {
+    fn default() -> Self {
+        ReconcileOptions {
+            date_window_days: 0,
+            amount_epsilon: EXACT,
+        }
+    }
+}
+
+/// Outcome of [`reconcile`]. Pairs are `(bank, ledger)`.
+#[derive(Debug, Clone, Default)]
+pub struct ReconcileReport<'a> {
+    pub matched: Vec<(&'a Transaction, &'a Transaction)>,
+    /// Same FITID on both sides but amounts outside the epsilon.
+    pub amount_mismatches: Vec<(&'a Transaction, &'a Transaction)>,
+    pub unmatched_bank: Vec<&'a Transaction>,
+    pub unmatched_ledger: Vec<&'a Transaction>,
+}
+
+/// Pairs bank transactions with ledger entries, each used at most once.
+///
+/// Transactions sharing a FITID are paired first; their amounts decide
+/// between `matched` and `amount_mismatches`. The rest match on amount
+/// within `amount_epsilon` and date within `date_window_days`, preferring
+/// the closest date and then ledger order.
+pub fn reconcile<'a>(
+    bank: &'a [Transaction],
+    ledger: &'a [Transaction],
+    options: ReconcileOptions,
+) -> ReconcileReport<'a> {
+    let mut report = ReconcileReport::default();
+    let mut ledger_used = vec![false; ledger.len()];
+    let mut bank_pending = Vec::new();
+
+    for bank_txn in bank {
+        let by_fitid = bank_txn.fitid.as_ref().and_then(|fitid| {
+            (0..ledger.len()).find(|&i| !ledger_used[i] && ledger[i].fitid.as_ref() == Some(fitid))
+        });
+        match by_fitid {
+            Some(i) => {
+                ledger_used[i] = true;
+                let pair = (bank_txn, &ledger[i]);
+                if amounts_equal(bank_txn.amount, ledger[i].amount, options.amount_epsilon) {
+                    report.matched.push(pair);
+                } else {
+                    report.amount_mismatches.push(pair);
+                }
+            }
+            None => bank_pending.push(bank_txn),
+        }
+    }
+
+    for bank_txn in bank_pending {
+        let days_apart = |i: usize| (bank_txn.date - ledger[i].date).num_days().unsigned_abs();
+        let closest = (0..ledger.len())
+            .filter(|&i| {
+                !ledger_used[i]
+                    && amounts_equal(bank_txn.amount, ledger[i].amount, options.amount_epsilon)
+                    && days_apart(i) <= options.date_window_days
+            })
+            .min_by_key(|&i| days_apart(i));
+        match closest {
+            Some(i) => {
+                ledger_used[i] = true;
+                report.matched.push((bank_txn, &ledger[i]));
+            }
+            None => report.unmatched_bank.push(bank_txn),
+        }
+    }
+
+    report.unmatched_ledger = ledger
+        .iter()
+        .zip(ledger_used)
+        .filter(|(_, used)| !used)
+        .map(|(transaction, _)| transaction)
+        .collect();
+    report
+}
+
+fn is_store_number(token: &str) -> bool {
+    token
+        .strip_prefix('#')
+        .is_some_and(|digits| !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::str::FromStr;
+
+    fn dec(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    #[rstest]
+    #[case("50.00", "50.00", "0", true)]
+    #[case("50.0", "50.00", "0", true)]
+    #[case("50.00", "50.01", "0", false)]
+    #[case("50.00", "50.0049", "0.005", true)]
+    #[case("50.00", "50.005", "0.005", true)]
+    #[case("50.00", "50.0051", "0.005", false)]
+    #[case("-50.00", "-49.999", "0.01", true)]
+    #[case("-50.00", "50.00", "0.01", false)]
+    #[case("50.00", "50.01", "-0.01", true)]
+    fn test_amounts_equal(
+        #[case] a: &str,
+        #[case] b: &str,
+        #[case] epsilon: &str,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(amounts_equal(dec(a), dec(b), dec(epsilon)), expected);
+        assert_eq!(amounts_equal(dec(b), dec(a), dec(epsilon)), expected);
+    }
+
+    #[rstest]
+    #[case("Coffee Shop", "COFFEE SHOP")]
+    #[case("  coffee    shop  ", "COFFEE SHOP")]
+    #[case("STARBUCKS #1234 SEATTLE", "STARBUCKS SEATTLE")]
+    #[case("NETFLIX.COM 866", "NETFLIX.COM")]
+    #[case("Acme Corp", "ACME")]
+    #[case("Widgets, Inc.", "WIDGETS")]
+    #[case("Smith,John", "SMITH JOHN")]
+    #[case("Foo Co. 12345", "FOO")]
+    #[case("7 ELEVEN 00123", "7 ELEVEN")]
+    #[case("#1234", "")]
+    #[case("", "")]
+    #[case("SQ *COFFEE SHOP      #1234 SEATTLE", "COFFEE SHOP SEATTLE")]
+    #[case("TST* Pizza Place 0042", "PIZZA PLACE")]
+    #[case("PAYPAL *NETFLIX.COM", "NETFLIX.COM")]
+    #[case("  sq *Bakery", "BAKERY")]
+    #[case("SQUARE ONE", "SQUARE ONE")]
+    fn test_normalize_payee(#[case] payee: &str, #[case] expected: &str) {
+        assert_eq!(normalize_payee(payee), expected);
+    }
+
+    fn transaction(amount: &str, currency: Option<&str>) -> Transaction {
+        Transaction {
+            date: NaiveDate::from_ymd_opt(2025, 12, 26).unwrap(),
+            user_date: None,
+            amount: dec(amount),
+            payee: None,
+            normalized_payee: None,
+            transaction_type: "DEBIT".to_string(),
+            fitid: None,
+            check_number: None,
+            status: None,
+            memo: None,
+            account_id: None,
+            currency: currency.map(str::to_string),
+            original_currency: None,
+            original_amount: None,
+            raw_source: None,
+        }
+    }
+
+    #[test]
+    fn test_net_by_currency() {
+        let transactions = [
+            transaction("-50.00", Some("USD")),
+            transaction("100.00", Some("EUR")),
+            transaction("20.00", Some("USD")),
+            transaction("5.00", None),
+        ];
+
+        let totals = net_by_currency(&transactions, "UNKNOWN");
+        assert_eq!(totals.len(), 3);
+        assert_eq!(totals["USD"], dec("-30.00"));
+        assert_eq!(totals["EUR"], dec("100.00"));
+        assert_eq!(totals["UNKNOWN"], dec("5.00"));
+    }
+
+    #[test]
+    fn test_net_by_currency_empty() {
+        assert!(net_by_currency(&[], "UNKNOWN").is_empty());
+    }
+
+    #[test]
+    fn test_field_completeness() {
+        let mut with_payee = transaction("-50.00", None);
+        with_payee.payee = Some("Coffee Shop".to_string());
+        with_payee.fitid = Some("1".to_string());
+
+        let mut memo_only = transaction("-20.00", None);
+        memo_only.memo = Some("POS purchase".to_string());
+        memo_only.transaction_type = "OTHER".to_string();
+
+        let mut bare = transaction("-5.00", None);
+        bare.transaction_type = " ".to_string();
+
+        let completeness = field_completeness(&[with_payee, memo_only, bare]);
+        assert_eq!(
+            completeness.payee,
+            PayeeCounts {
+                present: 1,
+                memo_only: 1,
+                missing: 1
+            }
+        );
+        assert_eq!(
+            completeness.fitid,
+            FitidCounts {
+                present: 1,
+                missing: 2
+            }
+        );
+        assert_eq!(
+            completeness.transaction_type,
+            TypeCounts {
+                specific: 1,
+                generic: 1,
+                missing: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_field_completeness_empty() {
+        assert_eq!(field_completeness(&[]), FieldCompleteness::default());
+    }
+
+    #[test]
+    fn test_transaction_summary() {
+        let transactions = [
+            Transaction {
+                date: ymd(2025, 12, 28),
+                ..transaction("-50.00", None)
+            },
+            Transaction {
+                date: ymd(2025, 12, 3),
+                ..transaction("1500.00", None)
+            },
+            transaction("-12.34", None),
+            transaction("0.00", None),
+        ];
+
+        let summary = TransactionSummary::from(&transactions[..]);
+        assert_eq!(summary.total_credits, dec("1500.00"));
+        assert_eq!(summary.total_debits, dec("-62.34"));
+        assert_eq!(summary.net, dec("1437.66"));
+        assert_eq!(summary.count, 4);
+        assert_eq!(
+            summary.date_range,
+            Some((ymd(2025, 12, 3), ymd(2025, 12, 28)))
+        );
+    }
+
+    #[test]
+    fn test_transaction_summary_empty() {
+        let summary = TransactionSummary::from(&[][..]);
+        assert_eq!(summary, TransactionSummary::default());
+        assert!(summary.net.is_zero());
+        assert_eq!(summary.date_range, None);
+    }
+
+    fn entry(day: u32, amount: &str, fitid: Option<&str>) -> Transaction {
+        Transaction {
+            date: ymd(2025, 12, day),
+            fitid: fitid.map(str::to_string),
+            ..transaction(amount, None)
+        }
+    }
+
+    fn fitids<'a>(transactions: impl IntoIterator<Item = &'a Transaction>) -> Vec<&'a str> {
+        transactions
+            .into_iter()
+            .map(|t| t.fitid.as_deref().unwrap_or("-"))
+            .collect()
+    }
+
+    #[test]
+    fn test_reconcile() {
+        let bank = [
+            entry(1, "-50.00", Some("A")),
+            entry(2, "-20.00", Some("B")),
+            entry(5, "-9.99", Some("C")),
+            entry(10, "-4.50", Some("D")),
+        ];
+        let ledger = [
+            entry(1, "-50.00", Some("A")),
+            entry(2, "-25.00", Some("B")),
+            entry(3, "-4.50", None),
+            entry(7, "-9.99", None),
+            entry(20, "-100.00", None),
+        ];
+
+        let report = reconcile(
+            &bank,
+            &ledger,
+            ReconcileOptions {
+                date_window_days: 2,
+                amount_epsilon: EXACT,
+            },
+        );
+
+        assert_eq!(
+            fitids(report.matched.iter().map(|(bank, _)| *bank)),
+            ["A", "C"]
+        );
+        assert_eq!(report.matched[1].1.date, ymd(2025, 12, 7));
+        assert_eq!(
+            fitids(report.amount_mismatches.iter().map(|(bank, _)| *bank)),
+            ["B"]
+        );
+        assert_eq!(fitids(report.unmatched_bank), ["D"]);
+        assert_eq!(
+            report
+                .unmatched_ledger
+                .iter()
+                .map(|t| t.amount)
+                .collect::<Vec<_>>(),
+            [dec("-4.50"), dec("-100.00")]
+        );
+    }
+
+    #[test]
+    fn test_reconcile_prefers_closest_date_and_uses_each_once() {
+        let bank = [entry(10, "-4.50", None), entry(10, "-4.50", None)];
+        let ledger = [entry(8, "-4.50", None), entry(11, "-4.50", None)];
+
+        let report = reconcile(
+            &bank,
+            &ledger,
+            ReconcileOptions {
+                date_window_days: 3,
+                amount_epsilon: dec("0.01"),
+            },
+        );
+
+        assert_eq!(report.matched[0].1.date, ymd(2025, 12, 11));
+        assert_eq!(report.matched[1].1.date, ymd(2025, 12, 8));
+        assert!(report.unmatched_bank.is_empty());
+        assert!(report.unmatched_ledger.is_empty());
+    }
+
+    fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[rstest]
+    #[case(Granularity::Daily, ymd(2025, 12, 26), ymd(2025, 12, 26))]
+    #[case(Granularity::Weekly, ymd(2025, 12, 26), ymd(2025, 12, 22))]
+    #[case(Granularity::Weekly, ymd(2025, 12, 22), ymd(2025, 12, 22))]
+    #[case(Granularity::Weekly, ymd(2026, 1, 1), ymd(2025, 12, 29))]
+    #[case(Granularity::Monthly, ymd(2025, 12, 26), ymd(2025, 12, 1))]
+    #[case(Granularity::Quarterly, ymd(2025, 12, 26), ymd(2025, 10, 1))]
+    #[case(Granularity::Quarterly, ymd(2025, 3, 31), ymd(2025, 1, 1))]
+    fn test_period_start(
+        #[case] granularity: Granularity,
+        #[case] date: NaiveDate,
+        #[case] expected: NaiveDate,
+    ) {
+        assert_eq!(granularity.period_start(date), expected);
+    }
+
+    #[test]
+    fn test_bucket_by_monthly() {
+        let dated = |date: NaiveDate, amount: &str| Transaction {
+            date,
+            ..transaction(amount, None)
+        };
+        let transactions = [
+            dated(ymd(2025, 11, 30), "-10.00"),
+            dated(ymd(2025, 12, 1), "-20.00"),
+            dated(ymd(2025, 12, 31), "100.00"),
+            dated(ymd(2026, 2, 14), "-5.00"),
+        ];
+
+        let buckets = bucket_by(&transactions, Granularity::Monthly);
+        assert_eq!(
+            buckets.into_iter().collect::<Vec<_>>(),
+            vec![
+                (ymd(2025, 11, 1), dec("-10.00")),
+                (ymd(2025, 12, 1), dec("80.00")),
+                (ymd(2026, 2, 1), dec("-5.00")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_amounts_equal_exact() {
+        assert!(amounts_equal(dec("1.2345"), dec("1.2345"), EXACT));
+        assert!(!amounts_equal(dec("1.2345"), dec("1.23"), EXACT));
+    }
+}
@@ -1,55 +1,157 @@
-use std::fs;
-
-use crate::{errors::StatementParseError, parsers::prelude::*, types::Transaction};
+use std::{
+    cell::RefCell,
+    cmp::Reverse,
+    collections::HashSet,
+    fs,
+    io::{Cursor, Read},
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use chrono::{Local, NaiveDate};
+use regex::Regex;
+use rust_decimal::Decimal;
+
+use crate::{
+    analysis::normalize_payee,
+    errors::{RowError, StatementParseError, StatementResult},
+    locale::NumberLocale,
+    parsers::{prelude::*, qfx::types::QfxDate},
+    types::{SortOrder, Transaction, TransactionFilter},
+    warnings::ParseWarning,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ParsedTransaction {
     Qfx(QfxTransaction),
+    Camt(CamtTransaction),
+    /// Produced by a parser registered with [`ParserBuilder::with_parser`].
+    Custom(Transaction),
 }
 
+/// Lowest confidence at which content-based detection picks a format.
+const DETECT_THRESHOLD: f32 = 0.5;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FileFormat {
     #[serde(rename = "qfx")]
     Qfx,
+    #[serde(rename = "camt053")]
+    Camt053,
 }
 
 impl FileFormat {
-    fn parse_raw(&self, content: &str) -> Result<Vec<ParsedTransaction>, StatementParseError> {
+    pub fn all() -> &'static [FileFormat] {
+        &[FileFormat::Qfx, FileFormat::Camt053]
+    }
+
+    pub fn extensions(&self) -> &'static [&'static str] {
+        match self {
+            FileFormat::Qfx => &["qfx", "ofx"],
+            FileFormat::Camt053 => &["xml"],
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            FileFormat::Qfx => "application/x-ofx",
+            FileFormat::Camt053 => "application/xml",
+        }
+    }
+
+    /// Rows in file order, including the ones that failed to parse.
+    fn parse_raw(
+        &self,
+        content: &str,
+        qfx_options: &QfxOptions,
+        on_warning: &mut dyn FnMut(&ParseWarning),
+    ) -> StatementResult<Vec<Result<ParsedTransaction, RowError>>> {
         match self {
             FileFormat::Qfx => {
-                let transactions =
-                    QfxParser::parse(content).map_err(StatementParseError::ParseFailed)?;
-                Ok(transactions
+                let qfx_options = QfxOptions {
+                    collect_row_errors: true,
+                    ..qfx_options.clone()
+                };
+                let statement =
+                    QfxParser::parse_statement_with_warnings(content, &qfx_options, on_warning)
+                        .map_err(StatementParseError::ParseFailed)?;
+                let mut row_errors = statement.row_errors.into_iter().peekable();
+                let mut rows = Vec::new();
+                for transaction in statement.transactions {
+                    while let Some(error) = row_errors.next_if(|error| error.row == rows.len() + 1)
+                    {
+                        rows.push(Err(error));
+                    }
+                    rows.push(Ok(ParsedTransaction::Qfx(transaction)));
+                }
+                rows.extend(row_errors.map(Err));
+                Ok(rows)
+            }
+            FileFormat::Camt053 => {
+                let rows =
+                    Camt053Parser::parse_rows(content).map_err(StatementParseError::ParseFailed)?;
+                Ok(rows
                     .into_iter()
-                    .map(ParsedTransaction::Qfx)
+                    .map(|row| row.map(ParsedTransaction::Camt))
                     .collect())
             }
         }
     }
 
-    fn parse<T>(&self, content: &str) -> Result<Vec<T>, StatementParseError>
-    where
-        T: TryFrom<ParsedTransaction, Error = StatementParseError>,
-    {
-        self.parse_raw(content)?
-            .into_iter()
-            .map(T::try_from)
-            .collect()
+    fn parse<T>(
+        &self,
+        content: &str,
+        qfx_options: &QfxOptions,
+        on_warning: &mut dyn FnMut(&ParseWarning),
+        row_errors: Option<&mut Vec<RowError>>,
+        convert: impl FnMut(ParsedTransaction, &mut dyn FnMut(&ParseWarning)) -> StatementResult<T>,
+    ) -> StatementResult<Vec<T>> {
+        let rows = self.parse_raw(content, qfx_options, on_warning)?;
+        convert_rows(rows, on_warning, row_errors, convert)
     }
 
-    fn detect(filename: Option<&str>, content: Option<&str>) -> Result<Self, StatementParseError> {
-        if let Some(content) = content {
-            if QfxParser::is_supported(filename, content) {
-                return Ok(FileFormat::Qfx);
-            }
+    /// Every format with its parser's confidence for `content`, highest
+    /// first. Formats with equal scores keep the order of [`FileFormat::all`].
+    pub fn detect_scored(filename: Option<&str>, content: &str) -> Vec<(FileFormat, f32)> {
+        let mut scores: Vec<(FileFormat, f32)> = Self::all()
+            .iter()
+            .map(|format| (*format, format.confidence(filename, content)))
+            .collect();
+        scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scores
+    }
+
+    fn confidence(&self, filename: Option<&str>, content: &str) -> f32 {
+        match self {
+            FileFormat::Qfx => QfxParser::confidence(filename, content),
+            FileFormat::Camt053 => Camt053Parser::confidence(filename, content),
         }
+    }
 
-        if let Some(filename) = filename {
-            if let Some(ext) = filename.split('.').last() {
-                if matches!(ext, "qfx" | "ofx") {
-                    return Ok(FileFormat::Qfx);
-                }
+    /// The best-scoring format at or above [`DETECT_THRESHOLD`]. The
+    /// filename extension is only used when no format recognises the
+    /// content at all, and `.xml` alone never picks camt.053, since any XML
+    /// file has it.
+    fn detect(filename: Option<&str>, content: Option<&str>) -> StatementResult<Self> {
+        if let Some(content) = content
+            && let Some((format, score)) = Self::detect_scored(filename, content).first()
+            && *score > 0.0
+        {
+            return if *score >= DETECT_THRESHOLD {
+                Ok(*format)
+            } else {
+                Err(StatementParseError::UnsupportedFormat)
+            };
+        }
+
+        if let Some(ext) = filename.and_then(|name| name.rsplit('.').next()) {
+            let ext = ext.to_lowercase();
+            if let Some(format) = Self::all()
+                .iter()
+                .find(|format| ext != "xml" && format.extensions().contains(&ext.as_str()))
+            {
+                return Ok(*format);
             }
         }
 
@@ -57,11 +159,67 @@ impl FileFormat {
     }
 }
 
+/// Parses QFX/OFX content with default options, skipping format detection.
+///
+/// Use [`ParserBuilder`] for anything configurable.
+pub fn parse_qfx(content: &str) -> StatementResult<Vec<Transaction>> {
+    FileFormat::Qfx.parse(
+        content,
+        &QfxOptions::default(),
+        &mut |_| {},
+        None,
+        |parsed, _| Transaction::try_from(parsed),
+    )
+}
+
+/// Outcome of [`ParserBuilder::validate_only`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationReport {
+    pub transaction_count: usize,
+    pub warnings: Vec<ParseWarning>,
+}
+
+/// Outcome of [`ParserBuilder::parse_report`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParseReport {
+    pub transactions: Vec<Transaction>,
+    pub errors: Vec<RowError>,
+}
+
+type WarningCallback = Box<dyn FnMut(&ParseWarning)>;
+type TransactionCallback = Box<dyn FnMut(&Transaction)>;
+type DateParser = Box<dyn Fn(&str) -> StatementResult<NaiveDate>>;
+type Clock = Box<dyn Fn() -> NaiveDate>;
+type Transform = Box<dyn Fn(Transaction) -> StatementResult<Option<Transaction>>>;
+
 #[derive(Default)]
 pub struct ParserBuilder {
     content: Option<String>,
-    filepath: Option<String>,
+    reader: Option<Box<dyn Read>>,
+    filepath: Option<PathBuf>,
     format: Option<FileFormat>,
+    qfx_options: QfxOptions,
+    normalize_payees: bool,
+    invert_amount_sign: bool,
+    normalize_sign: bool,
+    zero_normalize: bool,
+    dust_threshold: Option<Decimal>,
+    require_nonempty: bool,
+    date_from: Option<NaiveDate>,
+    date_to: Option<NaiveDate>,
+    filter: Option<TransactionFilter>,
+    dedup_consecutive: bool,
+    dedup_by_fitid: bool,
+    sort: SortOrder,
+    date_parser: Option<DateParser>,
+    clock: Option<Clock>,
+    lenient_dates: bool,
+    lenient: bool,
+    fitid_pattern: Option<Regex>,
+    parsers: Vec<Box<dyn DynParser>>,
+    transforms: Vec<Transform>,
+    on_transaction: Option<TransactionCallback>,
+    on_warning: Option<WarningCallback>,
 }
 
 impl ParserBuilder {
@@ -69,13 +227,38 @@ impl ParserBuilder {
         Self::default()
     }
 
+    /// Starts a builder for the file at `path`. The file is read when
+    /// parsing, and its name is used for format detection. A read failure
+    /// is reported as [`StatementParseError::ReadContentFailed`].
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            filepath: Some(path.as_ref().to_path_buf()),
+            ..Self::default()
+        }
+    }
+
     pub fn content(mut self, content: &str) -> Self {
         self.content = Some(content.to_string());
+        self.reader = None;
+        self
+    }
+
+    /// Like `content`, for raw bytes. Content that is not valid UTF-8 fails
+    /// the parse with [`StatementParseError::InvalidEncoding`].
+    pub fn content_bytes(self, content: &[u8]) -> Self {
+        self.reader(Cursor::new(content.to_vec()))
+    }
+
+    /// Reads the content from `reader` when parsing. Read errors and
+    /// invalid UTF-8 are reported by `parse`, as with `content_bytes`.
+    pub fn reader(mut self, reader: impl Read + 'static) -> Self {
+        self.reader = Some(Box::new(reader));
+        self.content = None;
         self
     }
 
     pub fn filename(mut self, filename: &str) -> Self {
-        self.filepath = Some(filename.to_string());
+        self.filepath = Some(PathBuf::from(filename));
         self
     }
 
@@ -84,34 +267,671 @@ impl ParserBuilder {
         self
     }
 
-    pub fn parse(self) -> Result<Vec<Transaction>, StatementParseError> {
-        self.parse_into::<Transaction>()
+    pub fn keep_raw_source(mut self, keep: bool) -> Self {
+        self.qfx_options.keep_raw_source = keep;
+        self
+    }
+
+    pub fn number_locale(mut self, locale: NumberLocale) -> Self {
+        self.qfx_options.number_locale = Some(locale);
+        self
+    }
+
+    /// When `TRNAMT` is zero or missing, takes the amount from the first
+    /// match of `regex` in `MEMO`, then `NAME`.
+    ///
+    /// A recovery fallback for broken exports such as `PAYMENT $50.00` with a
+    /// `0.00` amount; off by default. The first capture group is used if the
+    /// pattern has one, else the whole match, and it is read with the
+    /// `number_locale` rules. The sign is whatever the matched text says.
+    pub fn amount_from_memo_regex(mut self, regex: Regex) -> Self {
+        self.qfx_options.amount_from_memo = Some(regex);
+        self
+    }
+
+    /// Converts amounts that carry a `<CURRENCY><CURRATE>` into the
+    /// statement's `CURDEF` using the file's own rate (default off).
+    ///
+    /// The converted transaction's `currency` becomes `CURDEF`, and the
+    /// amount and currency as written go to `original_amount` and
+    /// `original_currency`.
+    pub fn apply_ofx_rates(mut self, apply: bool) -> Self {
+        self.qfx_options.apply_ofx_rates = apply;
+        self
+    }
+
+    pub fn normalize_payees(mut self, normalize: bool) -> Self {
+        self.normalize_payees = normalize;
+        self
+    }
+
+    /// Fails with [`StatementParseError::NoTransactions`] when nothing is
+    /// left to return, i.e. after `date_from`, `date_to`, `filter`, dedup
+    /// and any `transform` have run.
+    pub fn require_nonempty(mut self, require: bool) -> Self {
+        self.require_nonempty = require;
+        self
+    }
+
+    /// Keeps only transactions posted on or after `date`.
+    ///
+    /// QFX statements are deserialized whole, so this filters the parsed
+    /// transactions rather than skipping them while reading.
+    pub fn date_from(mut self, date: NaiveDate) -> Self {
+        self.date_from = Some(date);
+        self
+    }
+
+    /// Keeps only transactions posted on or before `date`.
+    pub fn date_to(mut self, date: NaiveDate) -> Self {
+        self.date_to = Some(date);
+        self
+    }
+
+    /// Keeps only transactions posted between `start` and `end`, inclusive.
+    /// Shorthand for `date_from(start).date_to(end)`.
+    pub fn date_range(self, start: NaiveDate, end: NaiveDate) -> Self {
+        self.date_from(start).date_to(end)
+    }
+
+    /// Keeps only transactions matching `filter`. Applied together with
+    /// `date_from` and `date_to`, before `dedup_consecutive`.
+    pub fn filter(mut self, filter: TransactionFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Drops a transaction when its date, amount, payee, memo and type match
+    /// the transaction right before it, for exports that double every row.
+    /// Repeats that are not adjacent are kept.
+    pub fn dedup_consecutive(mut self, dedup: bool) -> Self {
+        self.dedup_consecutive = dedup;
+        self
+    }
+
+    /// Keeps only the first transaction for each FITID, for overlapping
+    /// statements merged into one file. Transactions without a FITID are
+    /// always kept.
+    pub fn dedup_by_fitid(mut self, dedup: bool) -> Self {
+        self.dedup_by_fitid = dedup;
+        self
+    }
+
+    /// Orders the result by posting date. Ties keep their file order.
+    /// `SortOrder::None` (the default) leaves the file order as is.
+    pub fn sort(mut self, order: SortOrder) -> Self {
+        self.sort = order;
+        self
+    }
+
+    /// Parses every transaction date with `parser` instead of the built-in
+    /// format rules.
+    ///
+    /// The built-in parsing is not tried as a fallback, so `parser` sees the
+    /// raw date text (e.g. `20251226120000[-5:EST]`) and must handle all of
+    /// it. Applies to `parse()`; `parse_into` keeps the `TryFrom` conversion.
+    pub fn date_parser(
+        mut self,
+        parser: impl Fn(&str) -> StatementResult<NaiveDate> + 'static,
+    ) -> Self {
+        self.date_parser = Some(Box::new(parser));
+        self
+    }
+
+    /// Sets the source of today's date for time-relative checks, such as
+    /// [`ParseWarning::FutureDate`], so tests can use a fixed date. Defaults
+    /// to the local date.
+    pub fn clock(mut self, clock: impl Fn() -> NaiveDate + 'static) -> Self {
+        self.clock = Some(Box::new(clock));
+        self
+    }
+
+    fn today(&self) -> NaiveDate {
+        match &self.clock {
+            Some(clock) => clock(),
+            None => Local::now().date_naive(),
+        }
+    }
+
+    /// With `false`, a date whose day is past the end of its month (e.g.
+    /// `20250431`) is clamped to the month's last day instead of failing
+    /// the parse, and a [`ParseWarning::DateCorrected`] is reported. A
+    /// transaction with a date that cannot be read at all (e.g. `2025`) is
+    /// skipped with a [`ParseWarning::InvalidDate`].
+    ///
+    /// Strict by default. Ignored when a `date_parser` is set; applies to
+    /// `parse()` and `validate_only()`.
+    pub fn strict_dates(mut self, strict: bool) -> Self {
+        self.lenient_dates = !strict;
+        self
+    }
+
+    /// Registers a parser for a format this crate does not know. During
+    /// detection, registered parsers are tried in order before the built-in
+    /// formats; an explicit `format` bypasses them.
+    pub fn with_parser(mut self, parser: Box<dyn DynParser>) -> Self {
+        self.parsers.push(parser);
+        self
+    }
+
+    /// Skips transactions that fail to parse or convert instead of failing
+    /// the whole file. Use [`ParserBuilder::parse_report`] to see which rows
+    /// were skipped and why; `parse()` drops them silently.
+    ///
+    /// Errors after conversion, e.g. from a `transform`, still fail the
+    /// parse. Off by default.
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Signs each amount from its `TRNTYPE`, for exports that write every
+    /// amount as a positive magnitude: `DEBIT`, `FEE`, `ATM` and `POS`
+    /// become negative and `CREDIT` and `DEP` positive. Other types keep
+    /// their sign. Applied during conversion, before any other amount
+    /// handling.
+    pub fn normalize_sign(mut self, normalize: bool) -> Self {
+        self.normalize_sign = normalize;
+        self
+    }
+
+    /// Negates every amount, for exports that report spending as positive
+    /// and income as negative. `original_amount` is negated too so both
+    /// stay consistent. Applied after all other amount handling.
+    pub fn invert_amount_sign(mut self, invert: bool) -> Self {
+        self.invert_amount_sign = invert;
+        self
+    }
+
+    /// Rewrites negative zero (`-0.00`) amounts as positive zero, keeping
+    /// the scale, so they are not mistaken for debits. Applied after
+    /// `invert_amount_sign`.
+    pub fn zero_normalize(mut self, normalize: bool) -> Self {
+        self.zero_normalize = normalize;
+        self
+    }
+
+    /// Treats amounts whose absolute value is below `threshold` (e.g.
+    /// `0.01` for sub-cent dust) as positive zero. Also applies to
+    /// `original_amount`.
+    pub fn dust_threshold(mut self, threshold: Decimal) -> Self {
+        self.dust_threshold = Some(threshold);
+        self
+    }
+
+    /// Fails the parse with [`StatementParseError::FitidMismatch`] when a
+    /// transaction's FITID does not match `pattern`. Transactions without a
+    /// FITID are not checked. Anchor the pattern (e.g. `^\d{16}$`) to
+    /// require a full match.
+    pub fn fitid_pattern(mut self, pattern: Regex) -> Self {
+        self.fitid_pattern = Some(pattern);
+        self
+    }
+
+    /// Adds a step run on each transaction after every other `parse()`
+    /// option. Returning `Ok(None)` drops the transaction and an error
+    /// fails the parse. Transforms run in the order they were added.
+    pub fn transform(
+        mut self,
+        transform: impl Fn(Transaction) -> StatementResult<Option<Transaction>> + 'static,
+    ) -> Self {
+        self.transforms.push(Box::new(transform));
+        self
+    }
+
+    /// Calls `callback` with each transaction `parse()` returns, in order,
+    /// once every other option and `transform` has run. Useful for
+    /// progress and per-row metrics; the full `Vec` is still returned.
+    ///
+    /// Runs synchronously on the thread calling `parse`.
+    pub fn on_transaction(mut self, callback: impl FnMut(&Transaction) + 'static) -> Self {
+        self.on_transaction = Some(Box::new(callback));
+        self
+    }
+
+    /// Calls `callback` for each [`ParseWarning`] as it is found, for
+    /// logging or metrics without collecting a `Vec` first. This is the
+    /// builder's counterpart to [`QfxParser::parse_with_warnings`].
+    ///
+    /// The callback runs synchronously on the thread calling `parse`, in
+    /// the middle of parsing, so it should not block.
+    pub fn on_warning(mut self, callback: impl FnMut(&ParseWarning) + 'static) -> Self {
+        self.on_warning = Some(Box::new(callback));
+        self
+    }
+
+    pub fn parse(self) -> StatementResult<Vec<Transaction>> {
+        self.parse_report().map(|report| report.transactions)
+    }
+
+    /// Like `parse()`, also returning the rows skipped in
+    /// [`lenient`](ParserBuilder::lenient) mode. Without it, `errors` is
+    /// always empty.
+    pub fn parse_report(mut self) -> StatementResult<ParseReport> {
+        let mut errors = Vec::new();
+        let today = self.today();
+        let require_nonempty = self.require_nonempty;
+        let lenient = self.lenient;
+        let normalize_payees = self.normalize_payees;
+        let invert_amount_sign = self.invert_amount_sign;
+        let normalize_sign = self.normalize_sign;
+        let zero_normalize = self.zero_normalize;
+        let dust_threshold = self.dust_threshold;
+        let date_from = self.date_from;
+        let date_to = self.date_to;
+        let filter = self.filter.take();
+        let dedup_consecutive = self.dedup_consecutive;
+        let dedup_by_fitid = self.dedup_by_fitid;
+        let sort = self.sort;
+        let date_parser = self.date_parser.take();
+        let lenient_dates = self.lenient_dates;
+        let fitid_pattern = self.fitid_pattern.take();
+        let transforms = std::mem::take(&mut self.transforms);
+        let on_transaction = self.on_transaction.take();
+        let row_errors = lenient.then_some(&mut errors);
+        let mut transactions = self.parse_with(row_errors, |parsed, on_warning| {
+            convert_transaction(parsed, date_parser.as_ref(), lenient_dates, on_warning)?
+                .inspect(|transaction| check_future_date(transaction, today, on_warning))
+                .map(|transaction| {
+                    if normalize_sign {
+                        sign_from_type(transaction)
+                    } else {
+                        transaction
+                    }
+                })
+                .map(|transaction| check_fitid(transaction, fitid_pattern.as_ref()))
+                .transpose()
+        })?;
+
+        if let Some(date_from) = date_from {
+            transactions.retain(|transaction| transaction.date >= date_from);
+        }
+
+        if let Some(date_to) = date_to {
+            transactions.retain(|transaction| transaction.date <= date_to);
+        }
+
+        if let Some(filter) = filter {
+            transactions.retain(|transaction| filter.matches(transaction));
+        }
+
+        if dedup_consecutive {
+            transactions.dedup_by(|current, previous| {
+                current.date == previous.date
+                    && current.amount == previous.amount
+                    && current.payee == previous.payee
+                    && current.memo == previous.memo
+                    && current.transaction_type == previous.transaction_type
+            });
+        }
+
+        if dedup_by_fitid {
+            let mut seen = HashSet::new();
+            transactions.retain(|transaction| match &transaction.fitid {
+                Some(fitid) => seen.insert(fitid.clone()),
+                None => true,
+            });
+        }
+
+        if normalize_payees {
+            for transaction in &mut transactions {
+                transaction.normalized_payee = transaction
+                    .payee
+                    .as_deref()
+                    .map(normalize_payee)
+                    .filter(|payee| !payee.is_empty());
+            }
+        }
+
+        if invert_amount_sign {
+            for transaction in &mut transactions {
+                transaction.amount = -transaction.amount;
+                transaction.original_amount = transaction.original_amount.map(|amount| -amount);
+            }
+        }
+
+        if zero_normalize || dust_threshold.is_some() {
+            let normalize = |amount: Decimal| {
+                let is_dust = dust_threshold.is_some_and(|threshold| amount.abs() < threshold);
+                if is_dust || (zero_normalize && amount.is_zero()) {
+                    Decimal::new(0, amount.scale())
+                } else {
+                    amount
+                }
+            };
+            for transaction in &mut transactions {
+                transaction.amount = normalize(transaction.amount);
+                transaction.original_amount = transaction.original_amount.map(normalize);
+            }
+        }
+
+        match sort {
+            SortOrder::None => {}
+            SortOrder::DateAsc => transactions.sort_by_key(|transaction| transaction.date),
+            SortOrder::DateDesc => {
+                transactions.sort_by_key(|transaction| Reverse(transaction.date))
+            }
+        }
+
+        for transform in &transforms {
+            transactions = transactions
+                .into_iter()
+                .filter_map(|transaction| transform(transaction).transpose())
+                .collect::<StatementResult<_>>()?;
+        }
+
+        if require_nonempty && transactions.is_empty() {
+            return Err(StatementParseError::NoTransactions);
+        }
+
+        if let Some(callback) = on_transaction {
+            transactions.iter().for_each(callback);
+        }
+
+        Ok(ParseReport {
+            transactions,
+            errors,
+        })
+    }
+
+    /// Runs the same parse and conversion as `parse()` but keeps only the
+    /// transaction count and warnings. Any `on_warning` callback still runs.
+    ///
+    /// The count is taken before `date_from`, `date_to`, `filter`,
+    /// `dedup_consecutive`, `dedup_by_fitid` and any `transform`, so it
+    /// reflects everything in the file.
+    pub fn validate_only(mut self) -> StatementResult<ValidationReport> {
+        let warnings = Rc::new(RefCell::new(Vec::new()));
+        let mut callback = self.on_warning.take();
+        let collected = Rc::clone(&warnings);
+        self.on_warning = Some(Box::new(move |warning: &ParseWarning| {
+            collected.borrow_mut().push(warning.clone());
+            if let Some(callback) = callback.as_mut() {
+                callback(warning);
+            }
+        }));
+
+        let today = self.today();
+        let require_nonempty = self.require_nonempty;
+        let date_parser = self.date_parser.take();
+        let lenient_dates = self.lenient_dates;
+        let fitid_pattern = self.fitid_pattern.take();
+        let transaction_count = self
+            .parse_with(None, |parsed, on_warning| {
+                convert_transaction(parsed, date_parser.as_ref(), lenient_dates, on_warning)?
+                    .inspect(|transaction| check_future_date(transaction, today, on_warning))
+                    .map(|transaction| check_fitid(transaction, fitid_pattern.as_ref()).map(drop))
+                    .transpose()
+            })?
+            .len();
+        if require_nonempty && transaction_count == 0 {
+            return Err(StatementParseError::NoTransactions);
+        }
+
+        Ok(ValidationReport {
+            transaction_count,
+            warnings: warnings.take(),
+        })
     }
 
-    pub fn parse_into<T>(self) -> Result<Vec<T>, StatementParseError>
+    pub fn parse_into<T>(self) -> StatementResult<Vec<T>>
     where
         T: TryFrom<ParsedTransaction, Error = StatementParseError>,
     {
-        let format = self.format.map(Ok).unwrap_or_else(|| {
-            FileFormat::detect(self.filepath.as_deref(), self.content.as_deref())
-        })?;
+        let require_nonempty = self.require_nonempty;
+        let transactions = self.parse_with(None, |parsed, _| T::try_from(parsed).map(Some))?;
+        if require_nonempty && transactions.is_empty() {
+            return Err(StatementParseError::NoTransactions);
+        }
+        Ok(transactions)
+    }
 
-        let content = self.content.map(Ok).unwrap_or_else(|| {
-            self.filepath
-                .ok_or(StatementParseError::MissingContentAndFilepath)
-                .and_then(|path| fs::read_to_string(path).map_err(Into::into))
-        })?;
+    /// Shared by `parse`, `parse_into` and `validate_only`. `convert` may
+    /// return `Ok(None)` to skip a transaction. Failed rows are collected in
+    /// `row_errors` when given.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "parse", level = "debug", skip_all)
+    )]
+    fn parse_with<T>(
+        self,
+        row_errors: Option<&mut Vec<RowError>>,
+        convert: impl FnMut(
+            ParsedTransaction,
+            &mut dyn FnMut(&ParseWarning),
+        ) -> StatementResult<Option<T>>,
+    ) -> StatementResult<Vec<T>> {
+        let filename = self.filepath.as_deref().map(Path::to_string_lossy);
+        let filename = filename.as_deref();
+        let read_file = || {
+            let path = self
+                .filepath
+                .as_deref()
+                .ok_or(StatementParseError::MissingContentAndFilepath)?;
+            decode(fs::read(path)?).map(unwrap_mime)
+        };
+
+        let content = match (self.content, self.reader) {
+            (Some(content), _) => Some(content),
+            (None, Some(mut reader)) => {
+                let mut bytes = Vec::new();
+                reader.read_to_end(&mut bytes)?;
+                Some(decode(bytes)?)
+            }
+            (None, None) => None,
+        };
+
+        // Detection and registered parsers need the content, so read it early.
+        let mut content = content.map(unwrap_mime);
+        if content.is_none()
+            && self.format.is_none()
+            && (self.filepath.is_some() || !self.parsers.is_empty())
+        {
+            content = Some(read_file()?);
+        }
+        let custom_parser = match (self.format, &content) {
+            (None, Some(content)) => self
+                .parsers
+                .iter()
+                .find(|parser| parser.is_supported(filename, content)),
+            _ => None,
+        };
+        let format = match custom_parser {
+            Some(_) => None,
+            None => Some(
+                self.format
+                    .map(Ok)
+                    .unwrap_or_else(|| FileFormat::detect(filename, content.as_deref()))?,
+            ),
+        };
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            ?format,
+            custom = custom_parser.is_some(),
+            detected = self.format.is_none(),
+            "format selected"
+        );
+
+        let content = content.map(Ok).unwrap_or_else(read_file)?;
+
+        let mut callback = self.on_warning;
+        let mut on_warning = |warning: &ParseWarning| {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(%warning, "parse warning");
+            if let Some(callback) = callback.as_mut() {
+                callback(warning);
+            }
+        };
+
+        let transactions = match (custom_parser, format) {
+            (Some(parser), _) => {
+                let rows = parser
+                    .parse(&content)
+                    .map_err(StatementParseError::ParseFailed)?
+                    .into_iter()
+                    .map(|transaction| Ok(ParsedTransaction::Custom(transaction)))
+                    .collect();
+                convert_rows(rows, &mut on_warning, row_errors, convert)?
+            }
+            (None, format) => format
+                .ok_or(StatementParseError::UnsupportedFormat)?
+                .parse(
+                    &content,
+                    &self.qfx_options,
+                    &mut on_warning,
+                    row_errors,
+                    convert,
+                )?,
+        };
+        #[cfg(feature = "tracing")]
+        let converted = transactions.len();
+        let transactions: Vec<T> = transactions.into_iter().flatten().collect();
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            count = transactions.len(),
+            skipped = converted - transactions.len(),
+            "transactions parsed"
+        );
+
+        Ok(transactions)
+    }
+}
+
+fn check_future_date(
+    transaction: &Transaction,
+    today: NaiveDate,
+    on_warning: &mut dyn FnMut(&ParseWarning),
+) {
+    if transaction.date > today {
+        on_warning(&ParseWarning::FutureDate {
+            date: transaction.date,
+        });
+    }
+}
+
+/// The single place raw bytes become text, so an encoding step can be added
+/// here later.
+fn decode(bytes: Vec<u8>) -> StatementResult<String> {
+    String::from_utf8(bytes).map_err(Into::into)
+}
+
+/// With `row_errors`, rows that fail to parse or convert are recorded there
+/// and skipped. Without it the first failure is returned.
+fn convert_rows<T>(
+    rows: Vec<Result<ParsedTransaction, RowError>>,
+    on_warning: &mut dyn FnMut(&ParseWarning),
+    mut row_errors: Option<&mut Vec<RowError>>,
+    mut convert: impl FnMut(ParsedTransaction, &mut dyn FnMut(&ParseWarning)) -> StatementResult<T>,
+) -> StatementResult<Vec<T>> {
+    let mut transactions = Vec::new();
+    for (index, parsed) in rows.into_iter().enumerate() {
+        let error = match parsed.map(|parsed| convert(parsed, on_warning)) {
+            Ok(Ok(transaction)) => {
+                transactions.push(transaction);
+                continue;
+            }
+            Ok(Err(error)) if row_errors.is_none() => return Err(error),
+            Ok(Err(error)) => RowError {
+                row: index + 1,
+                message: error.to_string(),
+            },
+            Err(error) => error,
+        };
+        match row_errors.as_deref_mut() {
+            Some(row_errors) => row_errors.push(error),
+            None => {
+                return Err(StatementParseError::RowParseFailed {
+                    row: error.row,
+                    message: error.message,
+                });
+            }
+        }
+    }
+    Ok(transactions)
+}
+
+/// Converts to `Transaction`, reading dates with the custom `date_parser`
+/// if set, else clamping invalid days when `lenient_dates` is on. In
+/// lenient mode a transaction with an unreadable date is skipped.
+fn convert_transaction(
+    parsed: ParsedTransaction,
+    date_parser: Option<&DateParser>,
+    lenient_dates: bool,
+    on_warning: &mut dyn FnMut(&ParseWarning),
+) -> StatementResult<Option<Transaction>> {
+    match date_parser {
+        Some(date_parser) => {
+            Transaction::from_parsed_with_date_parser(parsed, &mut |raw| date_parser(raw)).map(Some)
+        }
+        // Clamping reads QFX date text; other formats convert as usual.
+        None if lenient_dates && matches!(parsed, ParsedTransaction::Qfx(_)) => {
+            let mut invalid = None;
+            let result = Transaction::from_parsed_with_date_parser(parsed, &mut |raw| {
+                let (date, clamped) = QfxDate::from(raw)
+                    .to_naive_date_clamped()
+                    .inspect_err(|_| invalid = Some(raw.to_string()))?;
+                if clamped {
+                    on_warning(&ParseWarning::DateCorrected {
+                        original: raw.to_string(),
+                        corrected: date,
+                    });
+                }
+                Ok(date)
+            });
+            match (result, invalid) {
+                (Err(_), Some(raw)) => {
+                    on_warning(&ParseWarning::InvalidDate { raw });
+                    Ok(None)
+                }
+                (result, _) => result.map(Some),
+            }
+        }
+        None => Transaction::try_from(parsed).map(Some),
+    }
+}
+
+fn sign_from_type(mut transaction: Transaction) -> Transaction {
+    if let Some(outflow) = transaction.kind().is_outflow() {
+        let sign = if outflow {
+            Decimal::NEGATIVE_ONE
+        } else {
+            Decimal::ONE
+        };
+        transaction.amount = transaction.amount.abs() * sign;
+        transaction.original_amount = transaction
+            .original_amount
+            .map(|amount| amount.abs() * sign);
+    }
+    transaction
+}
 
-        format.parse(&content)
+fn check_fitid(transaction: Transaction, pattern: Option<&Regex>) -> StatementResult<Transaction> {
+    match (&transaction.fitid, pattern) {
+        (Some(fitid), Some(pattern)) if !pattern.is_match(fitid) => {
+            Err(StatementParseError::FitidMismatch(fitid.clone()))
+        }
+        _ => Ok(transaction),
     }
 }
 
+/// Replaces a MIME message with its OFX attachment, if it has one.
+#[cfg(feature = "mime")]
+fn unwrap_mime(content: String) -> String {
+    crate::mime::extract_ofx_part(&content).unwrap_or(content)
+}
+
+#[cfg(not(feature = "mime"))]
+fn unwrap_mime(content: String) -> String {
+    content
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::TransactionOverrides;
     use rstest::rstest;
     use rust_decimal::Decimal;
-    use std::str::FromStr;
+    use std::{cell::RefCell, rc::Rc, str::FromStr};
 
     const SAMPLE_QFX: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
 <OFX>
@@ -172,10 +992,47 @@ mod tests {
         assert_eq!(builder.content.unwrap(), "test content");
     }
 
+    #[test]
+    fn test_builder_content_bytes_and_reader() {
+        let transactions = ParserBuilder::new()
+            .content_bytes(SAMPLE_QFX.as_bytes())
+            .parse()
+            .unwrap();
+        assert_eq!(transactions.len(), 1);
+
+        let transactions = ParserBuilder::new()
+            .reader(Cursor::new(SAMPLE_QFX))
+            .parse()
+            .unwrap();
+        assert_eq!(transactions[0].payee.as_deref(), Some("Coffee Shop"));
+    }
+
+    #[test]
+    fn test_builder_invalid_encoding() {
+        // "Calçadão" in Windows-1252.
+        let (before, after) = SAMPLE_QFX.split_once("Coffee Shop").unwrap();
+        let content = [before.as_bytes(), b"Cal\xe7ad\xe3o", after.as_bytes()].concat();
+
+        let result = ParserBuilder::new().content_bytes(&content).parse();
+        assert!(matches!(
+            result,
+            Err(StatementParseError::InvalidEncoding(_))
+        ));
+
+        let result = ParserBuilder::new()
+            .reader(Cursor::new(content))
+            .format(FileFormat::Qfx)
+            .parse();
+        assert!(matches!(
+            result,
+            Err(StatementParseError::InvalidEncoding(_))
+        ));
+    }
+
     #[test]
     fn test_builder_filename() {
         let builder = ParserBuilder::new().filename("test.qfx");
-        assert_eq!(builder.filepath.unwrap(), "test.qfx");
+        assert_eq!(builder.filepath.unwrap(), Path::new("test.qfx"));
     }
 
     #[test]
@@ -219,17 +1076,18 @@ mod tests {
 
     #[test]
     fn test_parse_raw_to_qfx_transaction() {
-        let result = FileFormat::Qfx.parse_raw(SAMPLE_QFX);
+        let result = FileFormat::Qfx.parse_raw(SAMPLE_QFX, &QfxOptions::default(), &mut |_| {});
 
         assert!(result.is_ok());
         let parsed = result.unwrap();
         assert_eq!(parsed.len(), 1);
 
         match &parsed[0] {
-            ParsedTransaction::Qfx(txn) => {
+            Ok(ParsedTransaction::Qfx(txn)) => {
                 assert_eq!(txn.trn_type, "DEBIT");
                 assert_eq!(txn.amount, Decimal::from_str("-50.00").unwrap());
             }
+            other => panic!("expected a QFX row, got {other:?}"),
         }
     }
 
@@ -247,41 +1105,996 @@ mod tests {
     }
 
     #[test]
-    fn test_parse_unsupported_format() {
-        let result = ParserBuilder::new()
-            .content("random content that's not OFX")
-            .parse();
+    fn test_builder_keep_raw_source() {
+        let transactions = ParserBuilder::new()
+            .content(SAMPLE_QFX)
+            .keep_raw_source(true)
+            .parse()
+            .unwrap();
 
-        assert!(result.is_err());
-        assert!(matches!(
-            result.unwrap_err(),
-            StatementParseError::UnsupportedFormat
-        ));
+        let raw = transactions[0].raw_source.as_deref().unwrap();
+        assert!(raw.contains("<NAME>Coffee Shop</NAME>"));
     }
 
     #[test]
-    fn test_parse_no_content_no_filepath() {
-        let result = ParserBuilder::new().format(FileFormat::Qfx).parse();
-
-        assert!(result.is_err());
+    fn test_builder_raw_source_off_by_default() {
+        let transactions = ParserBuilder::new().content(SAMPLE_QFX).parse().unwrap();
+        assert_eq!(transactions[0].raw_source, None);
     }
 
     #[test]
-    fn test_parse_invalid_content() {
-        let result = ParserBuilder::new()
-            .content("invalid QFX content")
-            .format(FileFormat::Qfx)
-            .parse();
+    fn test_builder_number_locale() {
+        let content = SAMPLE_QFX.replace("-50.00", "-1.050,00");
+
+        let transactions = ParserBuilder::new()
+            .content(&content)
+            .number_locale(NumberLocale::BrazilianPortuguese)
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            transactions[0].amount,
+            Decimal::from_str("-1050.00").unwrap()
+        );
+    }
 
-        assert!(result.is_err());
+    #[test]
+    fn test_builder_normalize_payees() {
+        let content = SAMPLE_QFX.replace("Coffee Shop", "Coffee  Shop #42");
+
+        let transactions = ParserBuilder::new()
+            .content(&content)
+            .normalize_payees(true)
+            .parse()
+            .unwrap();
+
+        assert_eq!(transactions[0].payee, Some("Coffee  Shop #42".to_string()));
+        assert_eq!(
+            transactions[0].normalized_payee,
+            Some("COFFEE SHOP".to_string())
+        );
     }
 
     #[rstest]
-    #[case(None, Some(SAMPLE_QFX), true)] // Detect by content
-    #[case(Some("statement.qfx"), None, true)] // Detect by .qfx extension
-    #[case(Some("statement.ofx"), None, true)] // Detect by .ofx extension
-    #[case(Some("statement.QFX"), Some(SAMPLE_QFX), true)] // Case insensitive with content
-    #[case(Some("statement.OFX"), Some(SAMPLE_QFX), true)] // Case insensitive with content
+    #[case(NaiveDate::from_ymd_opt(2025, 12, 25).unwrap(), true)]
+    #[case(NaiveDate::from_ymd_opt(2025, 12, 26).unwrap(), false)]
+    fn test_builder_clock_future_date(#[case] today: NaiveDate, #[case] future: bool) {
+        let report = ParserBuilder::new()
+            .content(SAMPLE_QFX)
+            .clock(move || today)
+            .validate_only()
+            .unwrap();
+
+        let expected = future.then(|| ParseWarning::FutureDate {
+            date: NaiveDate::from_ymd_opt(2025, 12, 26).unwrap(),
+        });
+        assert_eq!(report.warnings, Vec::from_iter(expected));
+    }
+
+    #[test]
+    fn test_builder_clock_future_date_on_parse() {
+        let warnings = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&warnings);
+
+        let transactions = ParserBuilder::new()
+            .content(SAMPLE_QFX)
+            .clock(|| NaiveDate::from_ymd_opt(2025, 1, 1).unwrap())
+            .on_warning(move |warning| sink.borrow_mut().push(warning.clone()))
+            .parse()
+            .unwrap();
+        assert_eq!(transactions.len(), 1);
+        assert!(matches!(
+            warnings.borrow().as_slice(),
+            [ParseWarning::FutureDate { .. }]
+        ));
+    }
+
+    #[test]
+    fn test_builder_clock_defaults_to_local_date() {
+        let before = Local::now().date_naive();
+        let today = ParserBuilder::new().today();
+        assert!(today >= before && today <= Local::now().date_naive());
+    }
+
+    #[test]
+    fn test_builder_normalize_payees_prefix_only() {
+        let content = SAMPLE_QFX.replace("Coffee Shop", "SQ *");
+
+        let transactions = ParserBuilder::new()
+            .content(&content)
+            .normalize_payees(true)
+            .parse()
+            .unwrap();
+
+        assert_eq!(transactions[0].normalized_payee, None);
+        assert_eq!(transactions[0].normalized_payee(), None);
+    }
+
+    #[test]
+    fn test_builder_normalize_payees_off_by_default() {
+        let transactions = ParserBuilder::new().content(SAMPLE_QFX).parse().unwrap();
+        assert_eq!(transactions[0].normalized_payee, None);
+    }
+
+    #[test]
+    fn test_builder_on_warning() {
+        let content = SAMPLE_QFX.replace(
+            "<STMTRS>",
+            "<STATUS><CODE>2000</CODE><SEVERITY>WARN</SEVERITY></STATUS><STMTRS>",
+        );
+        let warnings = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&warnings);
+
+        let transactions = ParserBuilder::new()
+            .content(&content)
+            .on_warning(move |warning| sink.borrow_mut().push(warning.clone()))
+            .parse()
+            .unwrap();
+
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(
+            *warnings.borrow(),
+            vec![ParseWarning::OfxStatus {
+                code: "2000".to_string(),
+                message: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_builder_on_warning_runs_during_parse() {
+        let content = SAMPLE_QFX.replace(
+            "<STMTRS>",
+            "<STATUS><CODE>2000</CODE><SEVERITY>WARN</SEVERITY></STATUS><STMTRS>",
+        );
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let warning_sink = Rc::clone(&events);
+        let transaction_sink = Rc::clone(&events);
+        let parse_thread = std::thread::current().id();
+
+        ParserBuilder::new()
+            .content(&content)
+            .on_warning(move |_| {
+                assert_eq!(std::thread::current().id(), parse_thread);
+                warning_sink.borrow_mut().push("warning");
+            })
+            .on_transaction(move |_| transaction_sink.borrow_mut().push("transaction"))
+            .parse()
+            .unwrap();
+
+        assert_eq!(*events.borrow(), ["warning", "transaction"]);
+    }
+
+    const EMPTY_QFX: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<OFX>
+    <BANKMSGSRSV1>
+        <STMTTRNRS>
+            <STMTRS>
+                <BANKTRANLIST>
+                </BANKTRANLIST>
+            </STMTRS>
+        </STMTTRNRS>
+    </BANKMSGSRSV1>
+</OFX>"#;
+
+    #[test]
+    fn test_builder_empty_result_allowed_by_default() {
+        let transactions = ParserBuilder::new().content(EMPTY_QFX).parse().unwrap();
+        assert!(transactions.is_empty());
+    }
+
+    #[test]
+    fn test_builder_require_nonempty() {
+        let result = ParserBuilder::new()
+            .content(EMPTY_QFX)
+            .require_nonempty(true)
+            .parse();
+        assert!(matches!(result, Err(StatementParseError::NoTransactions)));
+
+        let transactions = ParserBuilder::new()
+            .content(SAMPLE_QFX)
+            .require_nonempty(true)
+            .parse()
+            .unwrap();
+        assert_eq!(transactions.len(), 1);
+    }
+
+    #[rstest]
+    #[case::date_from(ParserBuilder::new().date_from(NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()))]
+    #[case::filter(ParserBuilder::new().filter(TransactionFilter {
+        min_amount: Some(Decimal::from(1000)),
+        ..TransactionFilter::default()
+    }))]
+    #[case::transform(ParserBuilder::new().transform(|_| Ok(None)))]
+    fn test_builder_require_nonempty_after_filtering(#[case] builder: ParserBuilder) {
+        let result = builder.content(SAMPLE_QFX).require_nonempty(true).parse();
+        assert!(matches!(result, Err(StatementParseError::NoTransactions)));
+    }
+
+    #[rstest]
+    #[case(2025, 12, 25, 1)]
+    #[case(2025, 12, 26, 1)]
+    #[case(2025, 12, 27, 0)]
+    fn test_builder_date_from(
+        #[case] year: i32,
+        #[case] month: u32,
+        #[case] day: u32,
+        #[case] expected: usize,
+    ) {
+        let transactions = ParserBuilder::new()
+            .content(SAMPLE_QFX)
+            .date_from(NaiveDate::from_ymd_opt(year, month, day).unwrap())
+            .parse()
+            .unwrap();
+        assert_eq!(transactions.len(), expected);
+    }
+
+    #[rstest]
+    #[case((2025, 12, 20), (2025, 12, 26), 1)]
+    #[case((2025, 12, 26), (2025, 12, 31), 1)]
+    #[case((2025, 12, 26), (2025, 12, 26), 1)]
+    #[case((2025, 12, 20), (2025, 12, 25), 0)]
+    #[case((2025, 12, 27), (2025, 12, 31), 0)]
+    fn test_builder_date_range(
+        #[case] start: (i32, u32, u32),
+        #[case] end: (i32, u32, u32),
+        #[case] expected: usize,
+    ) {
+        let date = |(year, month, day)| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+        let transactions = ParserBuilder::new()
+            .content(SAMPLE_QFX)
+            .date_range(date(start), date(end))
+            .parse()
+            .unwrap();
+        assert_eq!(transactions.len(), expected);
+    }
+
+    #[rstest]
+    #[case(2025, 12, 25, 0)]
+    #[case(2025, 12, 26, 1)]
+    #[case(2025, 12, 27, 1)]
+    fn test_builder_date_to(
+        #[case] year: i32,
+        #[case] month: u32,
+        #[case] day: u32,
+        #[case] expected: usize,
+    ) {
+        let transactions = ParserBuilder::new()
+            .content(SAMPLE_QFX)
+            .date_to(NaiveDate::from_ymd_opt(year, month, day).unwrap())
+            .parse()
+            .unwrap();
+        assert_eq!(transactions.len(), expected);
+    }
+
+    const DOUBLED_ROWS_QFX: &str = r#"OFXHEADER:100
+DATA:OFXSGML
+
+<OFX>
+<BANKMSGSRSV1>
+<STMTTRNRS>
+<STMTRS>
+<BANKTRANLIST>
+<STMTTRN>
+<TRNTYPE>DEBIT
+<DTPOSTED>20251226
+<TRNAMT>-4.50
+<NAME>Coffee Shop
+</STMTTRN>
+<STMTTRN>
+<TRNTYPE>DEBIT
+<DTPOSTED>20251226
+<TRNAMT>-4.50
+<NAME>Coffee Shop
+</STMTTRN>
+<STMTTRN>
+<TRNTYPE>DEBIT
+<DTPOSTED>20251226
+<TRNAMT>-12.00
+<NAME>Bakery
+</STMTTRN>
+<STMTTRN>
+<TRNTYPE>DEBIT
+<DTPOSTED>20251226
+<TRNAMT>-4.50
+<NAME>Coffee Shop
+</STMTTRN>
+</BANKTRANLIST>
+</STMTRS>
+</STMTTRNRS>
+</BANKMSGSRSV1>
+</OFX>"#;
+
+    #[test]
+    fn test_builder_lenient_collects_row_errors() {
+        let content = DOUBLED_ROWS_QFX
+            .replacen("<TRNAMT>-4.50", "<TRNAMT>abc", 1)
+            .replacen(
+                "<DTPOSTED>20251226\n<TRNAMT>-12.00",
+                "<DTPOSTED>2025\n<TRNAMT>-12.00",
+                1,
+            );
+
+        let report = ParserBuilder::new()
+            .content(&content)
+            .lenient(true)
+            .parse_report()
+            .unwrap();
+        assert_eq!(report.transactions.len(), 2);
+        let rows: Vec<_> = report.errors.iter().map(|error| error.row).collect();
+        assert_eq!(rows, [1, 3]);
+        assert!(report.errors[1].message.contains("date"));
+    }
+
+    #[test]
+    fn test_builder_reports_failed_row() {
+        let content = DOUBLED_ROWS_QFX.replace("<TRNAMT>-12.00", "<TRNAMT>12..00");
+
+        let error = ParserBuilder::new().content(&content).parse().unwrap_err();
+        assert!(matches!(
+            error,
+            StatementParseError::RowParseFailed { row: 3, .. }
+        ));
+        assert!(error.to_string().starts_with("Row 3: "));
+    }
+
+    #[test]
+    fn test_builder_strict_by_default_fails_on_bad_row() {
+        let content = DOUBLED_ROWS_QFX.replacen("<TRNAMT>-4.50", "<TRNAMT>abc", 1);
+
+        let result = ParserBuilder::new().content(&content).parse_report();
+        assert!(matches!(
+            result,
+            Err(StatementParseError::RowParseFailed { row: 1, .. })
+        ));
+
+        let transactions = ParserBuilder::new().content(&content).lenient(true).parse();
+        assert_eq!(transactions.unwrap().len(), 3);
+    }
+
+    #[rstest]
+    #[case(false, 4)]
+    #[case(true, 3)]
+    fn test_builder_dedup_consecutive(#[case] dedup: bool, #[case] expected: usize) {
+        let transactions = ParserBuilder::new()
+            .content(DOUBLED_ROWS_QFX)
+            .dedup_consecutive(dedup)
+            .parse()
+            .unwrap();
+        assert_eq!(transactions.len(), expected);
+    }
+
+    const NEWEST_FIRST_QFX: &str = r#"OFXHEADER:100
+DATA:OFXSGML
+
+<OFX>
+<BANKMSGSRSV1>
+<STMTTRNRS>
+<STMTRS>
+<BANKTRANLIST>
+<STMTTRN>
+<TRNTYPE>DEBIT
+<DTPOSTED>20251228
+<TRNAMT>-4.50
+<NAME>Coffee Shop
+</STMTTRN>
+<STMTTRN>
+<TRNTYPE>DEBIT
+<DTPOSTED>20251227
+<TRNAMT>-12.00
+<NAME>Bakery
+</STMTTRN>
+<STMTTRN>
+<TRNTYPE>CREDIT
+<DTPOSTED>20251227
+<TRNAMT>1500.00
+<NAME>Payroll
+</STMTTRN>
+<STMTTRN>
+<TRNTYPE>DEBIT
+<DTPOSTED>20251226
+<TRNAMT>-50.00
+<NAME>Grocer
+</STMTTRN>
+</BANKTRANLIST>
+</STMTRS>
+</STMTTRNRS>
+</BANKMSGSRSV1>
+</OFX>"#;
+
+    #[rstest]
+    #[case(SortOrder::None, ["Coffee Shop", "Bakery", "Payroll", "Grocer"])]
+    #[case(SortOrder::DateAsc, ["Grocer", "Bakery", "Payroll", "Coffee Shop"])]
+    #[case(SortOrder::DateDesc, ["Coffee Shop", "Bakery", "Payroll", "Grocer"])]
+    fn test_builder_sort(#[case] order: SortOrder, #[case] expected: [&str; 4]) {
+        let transactions = ParserBuilder::new()
+            .content(NEWEST_FIRST_QFX)
+            .sort(order)
+            .parse()
+            .unwrap();
+        let payees: Vec<_> = transactions
+            .iter()
+            .map(|t| t.payee.as_deref().unwrap())
+            .collect();
+        assert_eq!(payees, expected);
+    }
+
+    #[rstest]
+    #[case(false, 4)]
+    #[case(true, 3)]
+    fn test_builder_dedup_by_fitid(#[case] dedup: bool, #[case] expected: usize) {
+        let content = DOUBLED_ROWS_QFX
+            .replacen(
+                "<NAME>Coffee Shop",
+                "<FITID>202512260\n<NAME>Coffee Shop",
+                1,
+            )
+            .replacen("<NAME>Bakery", "<FITID>202512260\n<NAME>Bakery", 1);
+
+        let transactions = ParserBuilder::new()
+            .content(&content)
+            .dedup_by_fitid(dedup)
+            .parse()
+            .unwrap();
+        assert_eq!(transactions.len(), expected);
+        if dedup {
+            let fitids: Vec<_> = transactions.iter().map(|t| t.fitid.as_deref()).collect();
+            assert_eq!(fitids, [Some("202512260"), None, None]);
+            assert_eq!(transactions[0].payee.as_deref(), Some("Coffee Shop"));
+        }
+    }
+
+    #[rstest]
+    #[case("<TRNAMT>0.00</TRNAMT>", "PAYMENT $50.00", "50.00")]
+    #[case("", "PAYMENT $1,250.00", "1250.00")]
+    #[case("<TRNAMT>-12.00</TRNAMT>", "PAYMENT $50.00", "-12.00")]
+    #[case("<TRNAMT>0.00</TRNAMT>", "PAYMENT", "0.00")]
+    fn test_builder_amount_from_memo_regex(
+        #[case] trnamt: &str,
+        #[case] memo: &str,
+        #[case] expected: &str,
+    ) {
+        let content = SAMPLE_QFX
+            .replace("<TRNAMT>-50.00</TRNAMT>", trnamt)
+            .replace("Morning coffee", memo);
+
+        let transactions = ParserBuilder::new()
+            .content(&content)
+            .number_locale(NumberLocale::UsEnglish)
+            .amount_from_memo_regex(Regex::new(r"\$([\d,]+\.\d{2})").unwrap())
+            .parse()
+            .unwrap();
+        assert_eq!(transactions[0].amount, Decimal::from_str(expected).unwrap());
+    }
+
+    #[test]
+    fn test_builder_date_parser_overrides_builtin() {
+        let content = SAMPLE_QFX.replace("20251226120000", "2025-360");
+        assert!(ParserBuilder::new().content(&content).parse().is_err());
+
+        let transactions = ParserBuilder::new()
+            .content(&content)
+            .date_parser(|raw| {
+                let (year, ordinal) = raw
+                    .split_once('-')
+                    .ok_or(StatementParseError::QfxDateInvalidFormat)?;
+                NaiveDate::from_yo_opt(year.parse().unwrap(), ordinal.parse().unwrap())
+                    .ok_or(StatementParseError::QfxDateInvalidFormat)
+            })
+            .parse()
+            .unwrap();
+        assert_eq!(
+            transactions[0].date,
+            NaiveDate::from_ymd_opt(2025, 12, 26).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_builder_date_parser_error() {
+        let result = ParserBuilder::new()
+            .content(SAMPLE_QFX)
+            .date_parser(|_| Err(StatementParseError::QfxDateInvalidFormat))
+            .parse();
+        assert!(matches!(
+            result,
+            Err(StatementParseError::QfxDateInvalidFormat)
+        ));
+    }
+
+    /// Reads `PIPE` content with one amount per line, copying everything
+    /// else from the transaction in `SAMPLE_QFX`.
+    struct PipeParser;
+
+    impl DynParser for PipeParser {
+        fn parse(&self, content: &str) -> Result<Vec<Transaction>, String> {
+            let template = parse_qfx(SAMPLE_QFX).map_err(|e| e.to_string())?.remove(0);
+            content
+                .lines()
+                .skip(1)
+                .map(|line| {
+                    let amount = Decimal::from_str(line).map_err(|e| e.to_string())?;
+                    Ok(template.clone().with_overrides(TransactionOverrides {
+                        amount: Some(amount),
+                        ..Default::default()
+                    }))
+                })
+                .collect()
+        }
+
+        fn is_supported(&self, _filename: Option<&str>, content: &str) -> bool {
+            content.starts_with("PIPE\n")
+        }
+    }
+
+    #[test]
+    fn test_builder_with_parser() {
+        let transactions = ParserBuilder::new()
+            .content("PIPE\n-1.00\n2.50")
+            .with_parser(Box::new(PipeParser))
+            .invert_amount_sign(true)
+            .parse()
+            .unwrap();
+
+        let amounts: Vec<_> = transactions.iter().map(|t| t.amount.to_string()).collect();
+        assert_eq!(amounts, ["1.00", "-2.50"]);
+    }
+
+    #[test]
+    fn test_builder_with_parser_falls_back_to_builtin() {
+        let transactions = ParserBuilder::new()
+            .content(SAMPLE_QFX)
+            .with_parser(Box::new(PipeParser))
+            .parse()
+            .unwrap();
+        assert_eq!(transactions.len(), 1);
+
+        let result = ParserBuilder::new()
+            .content("PIPE\n-1.00")
+            .with_parser(Box::new(PipeParser))
+            .format(FileFormat::Qfx)
+            .parse();
+        assert!(matches!(result, Err(StatementParseError::ParseFailed(_))));
+    }
+
+    #[test]
+    fn test_builder_transforms_run_in_order() {
+        let transactions = ParserBuilder::new()
+            .content(DOUBLED_ROWS_QFX)
+            .transform(|mut transaction| {
+                transaction.payee = transaction.payee.map(|payee| payee.to_uppercase());
+                Ok(Some(transaction))
+            })
+            .transform(|transaction| {
+                Ok(transaction
+                    .payee
+                    .as_deref()
+                    .is_some_and(|payee| payee.starts_with("COFFEE"))
+                    .then_some(transaction))
+            })
+            .parse()
+            .unwrap();
+
+        let payees: Vec<_> = transactions
+            .iter()
+            .map(|transaction| transaction.payee.as_deref())
+            .collect();
+        assert_eq!(payees, [Some("COFFEE SHOP"); 3]);
+    }
+
+    #[test]
+    fn test_builder_transform_error_fails_parse() {
+        let result = ParserBuilder::new()
+            .content(SAMPLE_QFX)
+            .transform(|_| Err(StatementParseError::ParseFailed("rejected".to_string())))
+            .parse();
+        assert!(
+            matches!(result, Err(StatementParseError::ParseFailed(message)) if message == "rejected")
+        );
+    }
+
+    #[rstest]
+    #[case(r"^\d{9}$", None)]
+    #[case(r"^\d{16}$", Some("202512260"))]
+    fn test_builder_fitid_pattern(#[case] pattern: &str, #[case] rejected: Option<&str>) {
+        let result = ParserBuilder::new()
+            .content(SAMPLE_QFX)
+            .fitid_pattern(Regex::new(pattern).unwrap())
+            .parse();
+        match rejected {
+            None => assert_eq!(result.unwrap().len(), 1),
+            Some(fitid) => assert!(matches!(
+                result,
+                Err(StatementParseError::FitidMismatch(actual)) if actual == fitid
+            )),
+        }
+    }
+
+    #[test]
+    fn test_builder_validate_only_checks_fitid_pattern() {
+        let result = ParserBuilder::new()
+            .content(SAMPLE_QFX)
+            .fitid_pattern(Regex::new(r"^X").unwrap())
+            .validate_only();
+        assert!(matches!(result, Err(StatementParseError::FitidMismatch(_))));
+    }
+
+    #[test]
+    fn test_builder_invert_amount_sign() {
+        let amounts = |invert| {
+            ParserBuilder::new()
+                .content(DOUBLED_ROWS_QFX)
+                .invert_amount_sign(invert)
+                .parse()
+                .unwrap()
+                .into_iter()
+                .map(|transaction| transaction.amount)
+                .collect::<Vec<_>>()
+        };
+
+        let inverted: Vec<_> = amounts(false).into_iter().map(|amount| -amount).collect();
+        assert_eq!(amounts(true), inverted);
+    }
+
+    #[rstest]
+    #[case("DEBIT", "4.50", false, "4.50")]
+    #[case("DEBIT", "4.50", true, "-4.50")]
+    #[case("POS", "-4.50", true, "-4.50")]
+    #[case("CREDIT", "-4.50", true, "4.50")]
+    #[case("XFER", "4.50", true, "4.50")]
+    fn test_builder_normalize_sign(
+        #[case] trn_type: &str,
+        #[case] amount: &str,
+        #[case] normalize: bool,
+        #[case] expected: &str,
+    ) {
+        let content = DOUBLED_ROWS_QFX
+            .replacen("<TRNTYPE>DEBIT", &format!("<TRNTYPE>{trn_type}"), 1)
+            .replacen("<TRNAMT>-4.50", &format!("<TRNAMT>{amount}"), 1);
+
+        let transactions = ParserBuilder::new()
+            .content(&content)
+            .normalize_sign(normalize)
+            .parse()
+            .unwrap();
+        assert_eq!(transactions[0].amount.to_string(), expected);
+    }
+
+    #[test]
+    fn test_builder_filter() {
+        let transactions = ParserBuilder::new()
+            .content(DOUBLED_ROWS_QFX)
+            .filter(TransactionFilter {
+                max_amount: Some(Decimal::from_str("-5.00").unwrap()),
+                ..Default::default()
+            })
+            .parse()
+            .unwrap();
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].amount, Decimal::from_str("-12.00").unwrap());
+    }
+
+    const SHORT_DATE_QFX: &str = r#"OFXHEADER:100
+DATA:OFXSGML
+VERSION:102
+
+<OFX>
+<BANKMSGSRSV1>
+<STMTTRNRS>
+<STMTRS>
+<BANKTRANLIST>
+<STMTTRN>
+<TRNTYPE>DEBIT
+<DTPOSTED>20251226
+<TRNAMT>-4.50
+<FITID>1
+</STMTTRN>
+<STMTTRN>
+<TRNTYPE>DEBIT
+<DTPOSTED>2025
+<TRNAMT>-12.00
+<FITID>2
+</STMTTRN>
+<STMTTRN>
+<TRNTYPE>CREDIT
+<DTPOSTED>20251227
+<TRNAMT>100.00
+<FITID>3
+</STMTTRN>
+</BANKTRANLIST>
+</STMTRS>
+</STMTTRNRS>
+</BANKMSGSRSV1>
+</OFX>
+"#;
+
+    #[test]
+    fn test_builder_strict_dates_fail_on_short_date() {
+        let result = ParserBuilder::new().content(SHORT_DATE_QFX).parse();
+        assert!(matches!(
+            result,
+            Err(StatementParseError::QfxDateInvalidFormat)
+        ));
+    }
+
+    #[test]
+    fn test_builder_lenient_dates_skip_short_date() {
+        let warnings = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&warnings);
+
+        let transactions = ParserBuilder::new()
+            .content(SHORT_DATE_QFX)
+            .strict_dates(false)
+            .on_warning(move |warning| sink.borrow_mut().push(warning.clone()))
+            .parse()
+            .unwrap();
+
+        let fitids: Vec<_> = transactions
+            .iter()
+            .map(|transaction| transaction.fitid.as_deref())
+            .collect();
+        assert_eq!(fitids, [Some("1"), Some("3")]);
+        assert_eq!(
+            warnings.borrow().as_slice(),
+            [ParseWarning::InvalidDate {
+                raw: "2025".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_builder_lenient_dates_skipped_rows_not_counted() {
+        let report = ParserBuilder::new()
+            .content(SHORT_DATE_QFX)
+            .strict_dates(false)
+            .validate_only()
+            .unwrap();
+        assert_eq!(report.transaction_count, 2);
+        assert_eq!(report.warnings.len(), 1);
+
+        let only_bad_row = SHORT_DATE_QFX
+            .replace("<DTPOSTED>20251226", "<DTPOSTED>1")
+            .replace("<DTPOSTED>20251227", "<DTPOSTED>1");
+        let result = ParserBuilder::new()
+            .content(&only_bad_row)
+            .strict_dates(false)
+            .require_nonempty(true)
+            .parse();
+        assert!(matches!(result, Err(StatementParseError::NoTransactions)));
+    }
+
+    #[rstest]
+    #[case("-0.00", false, None, "0.00")]
+    #[case("0.00", true, None, "0.00")]
+    #[case("-0", false, None, "0")]
+    #[case("-0.004", false, Some("0.01"), "0.000")]
+    #[case("-0.01", false, Some("0.01"), "-0.01")]
+    #[case("-4.50", true, Some("0.01"), "4.50")]
+    fn test_builder_zero_normalize(
+        #[case] amount: &str,
+        #[case] invert: bool,
+        #[case] dust_threshold: Option<&str>,
+        #[case] expected: &str,
+    ) {
+        let content = DOUBLED_ROWS_QFX.replace("-12.00", amount);
+        let mut builder = ParserBuilder::new()
+            .content(&content)
+            .invert_amount_sign(invert)
+            .zero_normalize(true);
+        if let Some(threshold) = dust_threshold {
+            builder = builder.dust_threshold(Decimal::from_str(threshold).unwrap());
+        }
+
+        let transaction = builder.parse().unwrap().remove(2);
+        assert_eq!(transaction.amount.to_string(), expected);
+        assert_eq!(
+            transaction.amount.is_sign_negative(),
+            expected.starts_with('-')
+        );
+    }
+
+    #[test]
+    fn test_builder_on_transaction() {
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&seen);
+
+        let transactions = ParserBuilder::new()
+            .content(DOUBLED_ROWS_QFX)
+            .dedup_consecutive(true)
+            .on_transaction(move |transaction| sink.borrow_mut().push(transaction.amount))
+            .parse()
+            .unwrap();
+
+        let amounts: Vec<_> = transactions.iter().map(|t| t.amount).collect();
+        assert_eq!(*seen.borrow(), amounts);
+    }
+
+    #[test]
+    fn test_builder_strict_dates_by_default() {
+        let content = SAMPLE_QFX.replace("20251226120000", "20250431120000");
+        let result = ParserBuilder::new().content(&content).parse();
+        assert!(matches!(
+            result,
+            Err(StatementParseError::QfxDateInvalidFormat)
+        ));
+    }
+
+    #[test]
+    fn test_builder_lenient_dates_clamp_day() {
+        let content = SAMPLE_QFX.replace("20251226120000", "20250431120000");
+        let warnings = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&warnings);
+
+        let transactions = ParserBuilder::new()
+            .content(&content)
+            .strict_dates(false)
+            .on_warning(move |warning| sink.borrow_mut().push(warning.clone()))
+            .parse()
+            .unwrap();
+
+        let corrected = NaiveDate::from_ymd_opt(2025, 4, 30).unwrap();
+        assert_eq!(transactions[0].date, corrected);
+        assert_eq!(
+            warnings.borrow().as_slice(),
+            [ParseWarning::DateCorrected {
+                original: "20250431120000".to_string(),
+                corrected,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_builder_lenient_dates_valid_dates_unchanged() {
+        let report = ParserBuilder::new()
+            .content(SAMPLE_QFX)
+            .strict_dates(false)
+            .validate_only()
+            .unwrap();
+        assert!(report.warnings.is_empty());
+    }
+
+    #[cfg(feature = "mime")]
+    #[test]
+    fn test_builder_parses_ofx_mime_attachment() {
+        let content = format!(
+            "Content-Type: multipart/mixed; boundary=XYZ\n\n\
+             --XYZ\n\
+             Content-Type: text/plain\n\n\
+             Statement attached.\n\
+             --XYZ\n\
+             Content-Type: application/x-ofx\n\n\
+             {}\n\
+             --XYZ--\n",
+            SAMPLE_QFX
+        );
+
+        let transactions = ParserBuilder::new().content(&content).parse().unwrap();
+        assert_eq!(transactions.len(), 1);
+    }
+
+    #[test]
+    fn test_builder_validate_only() {
+        let report = ParserBuilder::new()
+            .content(DOUBLED_ROWS_QFX)
+            .dedup_consecutive(true)
+            .validate_only()
+            .unwrap();
+        assert_eq!(report.transaction_count, 4);
+        assert!(report.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_builder_validate_only_collects_warnings() {
+        let forwarded = Rc::new(RefCell::new(0));
+        let counter = Rc::clone(&forwarded);
+        let content = SAMPLE_QFX.replace(
+            "<STMTTRNRS>",
+            "<STMTTRNRS><STATUS><CODE>2000</CODE><SEVERITY>WARN</SEVERITY></STATUS>",
+        );
+
+        let report = ParserBuilder::new()
+            .content(&content)
+            .on_warning(move |_| *counter.borrow_mut() += 1)
+            .validate_only()
+            .unwrap();
+        assert_eq!(report.transaction_count, 1);
+        assert_eq!(report.warnings.len(), 1);
+        assert_eq!(*forwarded.borrow(), 1);
+    }
+
+    #[test]
+    fn test_builder_validate_only_error() {
+        let content = SAMPLE_QFX.replace("20251226120000", "not-a-date");
+        let result = ParserBuilder::new().content(&content).validate_only();
+        assert!(matches!(
+            result,
+            Err(StatementParseError::QfxDateInvalidFormat)
+        ));
+    }
+
+    #[test]
+    fn test_parse_unsupported_format() {
+        let result = ParserBuilder::new()
+            .content("random content that's not OFX")
+            .parse();
+
+        assert!(result.is_err());
+        assert!(matches!(
+            result.unwrap_err(),
+            StatementParseError::UnsupportedFormat
+        ));
+    }
+
+    #[rstest]
+    #[case(true)]
+    #[case(false)]
+    fn test_builder_garbage_user_date(#[case] strict: bool) {
+        let content = DOUBLED_ROWS_QFX.replacen(
+            "<DTPOSTED>20251226\n",
+            "<DTPOSTED>20251226\n<DTUSER>garbage\n",
+            1,
+        );
+
+        let transactions = ParserBuilder::new()
+            .content(&content)
+            .strict_dates(strict)
+            .parse()
+            .unwrap();
+        assert_eq!(transactions.len(), 4);
+        assert_eq!(
+            transactions[0].date,
+            NaiveDate::from_ymd_opt(2025, 12, 26).unwrap()
+        );
+        assert_eq!(transactions[0].user_date, None);
+    }
+
+    #[test]
+    fn test_builder_from_path() {
+        let path = std::env::temp_dir().join(format!(
+            "bank-statement-rs-from-path-{}.qfx",
+            std::process::id()
+        ));
+        fs::write(&path, SAMPLE_QFX).unwrap();
+
+        let result = ParserBuilder::from_path(&path).parse();
+        fs::remove_file(&path).unwrap();
+
+        let transactions = result.unwrap();
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].amount, Decimal::from_str("-50.00").unwrap());
+    }
+
+    #[test]
+    fn test_builder_from_path_missing_file() {
+        let result = ParserBuilder::from_path("/nonexistent/statement.qfx").parse();
+        assert!(matches!(
+            result.unwrap_err(),
+            StatementParseError::ReadContentFailed(_)
+        ));
+    }
+
+    #[test]
+    fn test_parse_no_content_no_filepath() {
+        let result = ParserBuilder::new().format(FileFormat::Qfx).parse();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_invalid_content() {
+        let result = ParserBuilder::new()
+            .content("invalid QFX content")
+            .format(FileFormat::Qfx)
+            .parse();
+
+        assert!(result.is_err());
+    }
+
+    #[rstest]
+    #[case(None, Some(SAMPLE_QFX), true)] // Detect by content
+    #[case(Some("statement.qfx"), None, true)] // Detect by .qfx extension
+    #[case(Some("statement.ofx"), None, true)] // Detect by .ofx extension
+    #[case(Some("statement.QFX"), Some(SAMPLE_QFX), true)] // Case insensitive with content
+    #[case(Some("statement.OFX"), Some(SAMPLE_QFX), true)] // Case insensitive with content
     #[case(Some("statement.csv"), Some("random content"), false)] // Unsupported
     #[case(None, None, false)] // No input
     #[case(Some("statement.txt"), Some("not ofx"), false)] // Unsupported content
@@ -305,23 +2118,40 @@ mod tests {
 
     #[test]
     fn test_file_format_parse_raw() {
-        let result = FileFormat::Qfx.parse_raw(SAMPLE_QFX);
+        let result = FileFormat::Qfx.parse_raw(SAMPLE_QFX, &QfxOptions::default(), &mut |_| {});
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
         assert_eq!(parsed.len(), 1);
 
         match &parsed[0] {
-            ParsedTransaction::Qfx(txn) => {
+            Ok(ParsedTransaction::Qfx(txn)) => {
                 assert_eq!(txn.trn_type, "DEBIT");
                 assert_eq!(txn.amount, Decimal::from_str("-50.00").unwrap());
             }
+            other => panic!("expected a QFX row, got {other:?}"),
         }
     }
 
+    #[test]
+    fn test_parse_qfx() {
+        let transactions = parse_qfx(SAMPLE_QFX).unwrap();
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].transaction_type, "DEBIT");
+
+        let result = parse_qfx("not OFX");
+        assert!(matches!(result, Err(StatementParseError::ParseFailed(_))));
+    }
+
     #[test]
     fn test_file_format_parse() {
-        let result = FileFormat::Qfx.parse::<Transaction>(SAMPLE_QFX);
+        let result = FileFormat::Qfx.parse(
+            SAMPLE_QFX,
+            &QfxOptions::default(),
+            &mut |_| {},
+            None,
+            |parsed, _| Transaction::try_from(parsed),
+        );
         assert!(result.is_ok());
 
         let transactions = result.unwrap();
@@ -334,10 +2164,18 @@ mod tests {
         let qfx_txn = QfxTransaction {
             trn_type: "DEBIT".to_string(),
             dt_posted: "20251226120000".into(),
+            dt_user: None,
             amount: Decimal::from_str("-50.00").unwrap(),
             fitid: Some("123".to_string()),
             name: Some("Test".to_string()),
             memo: Some("Memo".to_string()),
+            check_num: None,
+            ref_num: None,
+            account_id: None,
+            currency: None,
+            original_currency: None,
+            original_amount: None,
+            raw_source: None,
         };
 
         let parsed = ParsedTransaction::Qfx(qfx_txn);
@@ -347,6 +2185,7 @@ mod tests {
                 assert_eq!(txn.trn_type, "DEBIT");
                 assert_eq!(txn.amount, Decimal::from_str("-50.00").unwrap());
             }
+            other => panic!("expected a QFX transaction, got {other:?}"),
         }
     }
 
@@ -355,10 +2194,18 @@ mod tests {
         let qfx_txn = QfxTransaction {
             trn_type: "DEBIT".to_string(),
             dt_posted: "20251226120000".into(),
+            dt_user: None,
             amount: Decimal::from_str("-50.00").unwrap(),
             fitid: Some("123".to_string()),
             name: Some("Test".to_string()),
             memo: None,
+            check_num: None,
+            ref_num: None,
+            account_id: None,
+            currency: None,
+            original_currency: None,
+            original_amount: None,
+            raw_source: None,
         };
 
         let parsed = ParsedTransaction::Qfx(qfx_txn);
@@ -370,6 +2217,7 @@ mod tests {
             ParsedTransaction::Qfx(txn) => {
                 assert_eq!(txn.trn_type, "DEBIT");
             }
+            other => panic!("expected a QFX transaction, got {other:?}"),
         }
     }
 
@@ -383,6 +2231,185 @@ mod tests {
         assert_eq!(deserialized, FileFormat::Qfx);
     }
 
+    #[test]
+    fn test_file_format_all() {
+        assert_eq!(FileFormat::all(), &[FileFormat::Qfx, FileFormat::Camt053]);
+    }
+
+    #[test]
+    fn test_file_format_extensions() {
+        assert_eq!(FileFormat::Qfx.extensions(), &["qfx", "ofx"]);
+    }
+
+    #[test]
+    fn test_file_format_mime_type() {
+        assert_eq!(FileFormat::Qfx.mime_type(), "application/x-ofx");
+    }
+
+    #[test]
+    fn test_file_format_all_have_metadata() {
+        for format in FileFormat::all() {
+            assert!(!format.extensions().is_empty());
+            assert!(!format.mime_type().is_empty());
+        }
+    }
+
+    const SAMPLE_CAMT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.053.001.02">
+    <BkToCstmrStmt>
+        <Stmt>
+            <Ntry>
+                <Amt Ccy="EUR">50.00</Amt>
+                <CdtDbtInd>DBIT</CdtDbtInd>
+                <BookgDt><Dt>2025-12-26</Dt></BookgDt>
+                <NtryDtls><TxDtls><RmtInf><Ustrd>Coffee Shop</Ustrd></RmtInf></TxDtls></NtryDtls>
+            </Ntry>
+            <Ntry>
+                <Amt Ccy="EUR">1500.00</Amt>
+                <CdtDbtInd>CRDT</CdtDbtInd>
+                <BookgDt><Dt>2025-12-27</Dt></BookgDt>
+            </Ntry>
+        </Stmt>
+    </BkToCstmrStmt>
+</Document>"#;
+
+    #[test]
+    fn test_builder_parse_camt053() {
+        assert_eq!(
+            FileFormat::detect(None, Some(SAMPLE_CAMT)).unwrap(),
+            FileFormat::Camt053
+        );
+
+        let transactions = ParserBuilder::new().content(SAMPLE_CAMT).parse().unwrap();
+        assert_eq!(transactions.len(), 2);
+        assert_eq!(transactions[0].amount, Decimal::from_str("-50.00").unwrap());
+        assert_eq!(transactions[0].payee.as_deref(), Some("Coffee Shop"));
+        assert_eq!(
+            transactions[0].date,
+            NaiveDate::from_ymd_opt(2025, 12, 26).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_builder_camt053_row_errors() {
+        let content = SAMPLE_CAMT.replacen("50.00", "abc", 1);
+
+        let report = ParserBuilder::new()
+            .content(&content)
+            .lenient(true)
+            .parse_report()
+            .unwrap();
+        assert_eq!(report.transactions.len(), 1);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].row, 1);
+
+        let result = ParserBuilder::new().content(&content).parse();
+        assert!(matches!(
+            result.unwrap_err(),
+            StatementParseError::RowParseFailed { row: 1, .. }
+        ));
+    }
+
+    #[rstest]
+    #[case(false)]
+    #[case(true)]
+    fn test_builder_camt053_without_entries(#[case] require_nonempty: bool) {
+        let content = SAMPLE_CAMT.replace(
+            &SAMPLE_CAMT[SAMPLE_CAMT.find("<Ntry>").unwrap()
+                ..SAMPLE_CAMT.rfind("</Ntry>").unwrap() + "</Ntry>".len()],
+            "",
+        );
+
+        let result = ParserBuilder::new()
+            .content(&content)
+            .require_nonempty(require_nonempty)
+            .parse();
+        if require_nonempty {
+            assert!(matches!(
+                result.unwrap_err(),
+                StatementParseError::NoTransactions
+            ));
+        } else {
+            assert!(result.unwrap().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_detect_scored_prefers_root_element() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.053.001.02">
+    <BkToCstmrStmt>
+        <Stmt>
+            <Ntry>
+                <Amt Ccy="EUR">10.00</Amt>
+                <CdtDbtInd>CRDT</CdtDbtInd>
+                <BookgDt><Dt>2025-12-26</Dt></BookgDt>
+                <NtryDtls><TxDtls><RmtInf><Ustrd><![CDATA[Refund <OFX> import]]></Ustrd></RmtInf></TxDtls></NtryDtls>
+            </Ntry>
+        </Stmt>
+    </BkToCstmrStmt>
+</Document>"#;
+        let scores = FileFormat::detect_scored(None, content);
+        assert_eq!(
+            scores,
+            vec![(FileFormat::Camt053, 1.0), (FileFormat::Qfx, 0.5)]
+        );
+        assert_eq!(
+            FileFormat::detect(None, Some(content)).unwrap(),
+            FileFormat::Camt053
+        );
+    }
+
+    #[rstest]
+    #[case(Some("statement.xml"), "random content")]
+    #[case(Some("statement.xml"), "<?xml version=\"1.0\"?><OFXX></OFXX>")]
+    #[case(None, "random content")]
+    fn test_detect_xml_without_candidate_is_unsupported(
+        #[case] filename: Option<&str>,
+        #[case] content: &str,
+    ) {
+        let scores = FileFormat::detect_scored(filename, content);
+        assert!(scores.iter().all(|(_, score)| *score == 0.0));
+        assert!(matches!(
+            FileFormat::detect(filename, Some(content)),
+            Err(StatementParseError::UnsupportedFormat)
+        ));
+    }
+
+    #[test]
+    fn test_builder_unrecognised_xml_is_unsupported() {
+        let result = ParserBuilder::new()
+            .content("<?xml version=\"1.0\"?><OFXX></OFXX>")
+            .filename("statement.xml")
+            .parse();
+        assert!(matches!(
+            result,
+            Err(StatementParseError::UnsupportedFormat)
+        ));
+    }
+
+    #[test]
+    fn test_builder_from_path_detects_camt_by_content() {
+        let path = std::env::temp_dir().join(format!(
+            "bank-statement-rs-from-path-{}.xml",
+            std::process::id()
+        ));
+        fs::write(&path, SAMPLE_CAMT).unwrap();
+
+        let result = ParserBuilder::from_path(&path).parse();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(result.unwrap().len(), 2);
+    }
+
+    #[rstest]
+    #[case("statement.QFX")]
+    #[case("statement.Ofx")]
+    fn test_file_format_detect_extension_case_insensitive(#[case] filename: &str) {
+        let result = FileFormat::detect(Some(filename), None);
+        assert_eq!(result.unwrap(), FileFormat::Qfx);
+    }
+
     #[test]
     fn test_file_format_debug() {
         let format = FileFormat::Qfx;
@@ -395,10 +2422,18 @@ mod tests {
         let qfx_txn = QfxTransaction {
             trn_type: "DEBIT".to_string(),
             dt_posted: "20251226120000".into(),
+            dt_user: None,
             amount: Decimal::from_str("-50.00").unwrap(),
             fitid: None,
             name: None,
             memo: None,
+            check_num: None,
+            ref_num: None,
+            account_id: None,
+            currency: None,
+            original_currency: None,
+            original_amount: None,
+            raw_source: None,
         };
 
         let parsed = ParsedTransaction::Qfx(qfx_txn);
@@ -411,10 +2446,18 @@ mod tests {
         let qfx_txn = QfxTransaction {
             trn_type: "DEBIT".to_string(),
             dt_posted: "20251226120000".into(),
+            dt_user: None,
             amount: Decimal::from_str("-50.00").unwrap(),
             fitid: None,
             name: None,
             memo: None,
+            check_num: None,
+            ref_num: None,
+            account_id: None,
+            currency: None,
+            original_currency: None,
+            original_amount: None,
+            raw_source: None,
         };
 
         let parsed = ParsedTransaction::Qfx(qfx_txn);
@@ -425,6 +2468,7 @@ mod tests {
                 assert_eq!(a.trn_type, b.trn_type);
                 assert_eq!(a.amount, b.amount);
             }
+            other => panic!("expected QFX transactions, got {other:?}"),
         }
     }
 
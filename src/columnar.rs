@@ -0,0 +1,173 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{ArrayRef, Date32Array, Decimal128Array, StringArray},
+    datatypes::{DataType, Date32Type, Field, Schema},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+
+use crate::Transaction;
+use rust_decimal::Decimal;
+
+/// Converts transactions into one Arrow [`RecordBatch`], one column per
+/// `Transaction` field.
+///
+/// Dates are `Date32` and strings are nullable `Utf8`. Both amount columns
+/// are `Decimal128(38, scale)` where `scale` is the largest scale among the
+/// amounts (2 for an empty slice), so no digits are lost.
+pub fn to_record_batch(transactions: &[Transaction]) -> Result<RecordBatch, ArrowError> {
+    let scale = transactions
+        .iter()
+        .flat_map(|t| [Some(t.amount), t.original_amount])
+        .flatten()
+        .map(|amount| amount.scale())
+        .max()
+        .unwrap_or(2);
+    let amount_type = DataType::Decimal128(38, scale as i8);
+
+    let schema = Schema::new(vec![
+        Field::new("date", DataType::Date32, false),
+        Field::new("user_date", DataType::Date32, true),
+        Field::new("amount", amount_type.clone(), false),
+        Field::new("payee", DataType::Utf8, true),
+        Field::new("normalized_payee", DataType::Utf8, true),
+        Field::new("transaction_type", DataType::Utf8, false),
+        Field::new("fitid", DataType::Utf8, true),
+        Field::new("check_number", DataType::Utf8, true),
+        Field::new("status", DataType::Utf8, true),
+        Field::new("memo", DataType::Utf8, true),
+        Field::new("account_id", DataType::Utf8, true),
+        Field::new("currency", DataType::Utf8, true),
+        Field::new("original_currency", DataType::Utf8, true),
+        Field::new("original_amount", amount_type.clone(), true),
+        Field::new("raw_source", DataType::Utf8, true),
+    ]);
+
+    let mantissa = |mut amount: Decimal| {
+        amount.rescale(scale);
+        amount.mantissa()
+    };
+    let amounts = transactions.iter().map(|t| mantissa(t.amount));
+    let original_amounts = transactions.iter().map(|t| t.original_amount.map(mantissa));
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(Date32Array::from_iter_values(
+            transactions
+                .iter()
+                .map(|t| Date32Type::from_naive_date(t.date)),
+        )),
+        Arc::new(Date32Array::from_iter(
+            transactions
+                .iter()
+                .map(|t| t.user_date.map(Date32Type::from_naive_date)),
+        )),
+        Arc::new(Decimal128Array::from_iter_values(amounts).with_data_type(amount_type.clone())),
+        string_column(transactions, |t| t.payee.as_deref()),
+        string_column(transactions, |t| t.normalized_payee.as_deref()),
+        string_column(transactions, |t| Some(&t.transaction_type)),
+        string_column(transactions, |t| t.fitid.as_deref()),
+        string_column(transactions, |t| t.check_number.as_deref()),
+        string_column(transactions, |t| t.status.as_deref()),
+        string_column(transactions, |t| t.memo.as_deref()),
+        string_column(transactions, |t| t.account_id.as_deref()),
+        string_column(transactions, |t| t.currency.as_deref()),
+        string_column(transactions, |t| t.original_currency.as_deref()),
+        Arc::new(Decimal128Array::from_iter(original_amounts).with_data_type(amount_type)),
+        string_column(transactions, |t| t.raw_source.as_deref()),
+    ];
+
+    RecordBatch::try_new(Arc::new(schema), columns)
+}
+
+fn string_column(
+    transactions: &[Transaction],
+    field: impl Fn(&Transaction) -> Option<&str>,
+) -> ArrayRef {
+    Arc::new(StringArray::from_iter(transactions.iter().map(field)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::Array;
+    use chrono::NaiveDate;
+    use std::str::FromStr;
+
+    fn transaction(amount: &str, payee: Option<&str>) -> Transaction {
+        Transaction {
+            date: NaiveDate::from_ymd_opt(2025, 12, 26).unwrap(),
+            user_date: None,
+            amount: Decimal::from_str(amount).unwrap(),
+            payee: payee.map(str::to_string),
+            normalized_payee: None,
+            transaction_type: "DEBIT".to_string(),
+            fitid: None,
+            check_number: None,
+            status: None,
+            memo: None,
+            account_id: None,
+            currency: Some("USD".to_string()),
+            original_currency: None,
+            original_amount: None,
+            raw_source: None,
+        }
+    }
+
+    #[test]
+    fn test_to_record_batch() {
+        let transactions = [
+            transaction("-50.00", Some("Coffee Shop")),
+            transaction("1500.125", None),
+        ];
+
+        let batch = to_record_batch(&transactions).unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.num_columns(), 15);
+
+        let dates = batch
+            .column_by_name("date")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<Date32Array>()
+            .unwrap();
+        assert_eq!(
+            dates.value_as_date(0),
+            NaiveDate::from_ymd_opt(2025, 12, 26)
+        );
+
+        let amounts = batch
+            .column_by_name("amount")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<Decimal128Array>()
+            .unwrap();
+        assert_eq!(amounts.data_type(), &DataType::Decimal128(38, 3));
+        assert_eq!(amounts.value_as_string(0), "-50.000");
+        assert_eq!(amounts.value_as_string(1), "1500.125");
+
+        let payees = batch
+            .column_by_name("payee")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(payees.value(0), "Coffee Shop");
+        assert!(payees.is_null(1));
+        assert!(batch.column_by_name("user_date").unwrap().is_null(0));
+    }
+
+    #[test]
+    fn test_to_record_batch_empty() {
+        let batch = to_record_batch(&[]).unwrap();
+        assert_eq!(batch.num_rows(), 0);
+        assert_eq!(
+            batch
+                .schema()
+                .field_with_name("amount")
+                .unwrap()
+                .data_type(),
+            &DataType::Decimal128(38, 2)
+        );
+    }
+}
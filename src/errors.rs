@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -10,6 +11,31 @@ pub enum StatementParseError {
     ReadContentFailed(#[from] std::io::Error),
     #[error("Content or filepath is required")]
     MissingContentAndFilepath,
+    #[error("No transactions found")]
+    NoTransactions,
     #[error("QFX date invalid format")]
     QfxDateInvalidFormat,
+    #[error("CAMT date invalid format")]
+    CamtDateInvalidFormat,
+    #[error("FITID {0} does not match the required pattern")]
+    FitidMismatch(String),
+    #[error("Content is not valid UTF-8: {0}")]
+    InvalidEncoding(#[from] std::string::FromUtf8Error),
+    #[error("CSV error: {0}")]
+    Csv(#[from] csv::Error),
+    /// `row` is the transaction's 1-based number in the file, as in
+    /// [`RowError`]. `message` is the underlying error's text.
+    #[error("Row {row}: {message}")]
+    RowParseFailed { row: usize, message: String },
 }
+
+/// A single transaction that failed to parse in lenient mode. `row` is its
+/// 1-based number in the file, counting every transaction element.
+#[derive(Error, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[error("Row {row}: {message}")]
+pub struct RowError {
+    pub row: usize,
+    pub message: String,
+}
+
+pub type StatementResult<T> = Result<T, StatementParseError>;
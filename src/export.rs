@@ -0,0 +1,135 @@
+use std::io::Write;
+
+use crate::{Transaction, errors::StatementResult};
+
+const CSV_HEADER: [&str; 6] = ["Date", "Type", "Description", "Amount", "FITID", "Memo"];
+
+/// Account type written in a QIF file's `!Type:` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QifAccountType {
+    #[default]
+    Bank,
+    Cash,
+    CreditCard,
+    OtherAsset,
+    OtherLiability,
+}
+
+impl QifAccountType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QifAccountType::Bank => "Bank",
+            QifAccountType::Cash => "Cash",
+            QifAccountType::CreditCard => "CCard",
+            QifAccountType::OtherAsset => "Oth A",
+            QifAccountType::OtherLiability => "Oth L",
+        }
+    }
+}
+
+/// Writes transactions as QIF for import into budgeting tools.
+///
+/// Each transaction gets a `D` (date, `MM/DD/YYYY`), `T` (amount) and,
+/// when present, `P` (payee) and `M` (memo) line, and ends with `^`.
+pub fn write_qif(transactions: &[Transaction], account_type: QifAccountType) -> String {
+    let mut qif = format!("!Type:{}\n", account_type.as_str());
+    for transaction in transactions {
+        qif.push_str(&format!("D{}\n", transaction.date.format("%m/%d/%Y")));
+        qif.push_str(&format!("T{}\n", transaction.amount));
+        if let Some(payee) = &transaction.payee {
+            qif.push_str(&format!("P{}\n", single_line(payee)));
+        }
+        if let Some(memo) = &transaction.memo {
+            qif.push_str(&format!("M{}\n", single_line(memo)));
+        }
+        qif.push_str("^\n");
+    }
+    qif
+}
+
+/// Writes transactions as CSV with a `Date,Type,Description,Amount,FITID,Memo`
+/// header and one [`Transaction::to_csv_record`] row each.
+pub fn write_csv<W: Write>(transactions: &[Transaction], writer: W) -> StatementResult<()> {
+    let mut csv = csv::Writer::from_writer(writer);
+    csv.write_record(CSV_HEADER)?;
+    for transaction in transactions {
+        csv.write_record(transaction.to_csv_record())?;
+    }
+    csv.flush()?;
+    Ok(())
+}
+
+/// QIF fields are one line each, so embedded line breaks become spaces.
+fn single_line(value: &str) -> String {
+    value.replace(['\r', '\n'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use rstest::rstest;
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    fn transaction(amount: &str, payee: Option<&str>, memo: Option<&str>) -> Transaction {
+        Transaction {
+            date: NaiveDate::from_ymd_opt(2025, 12, 26).unwrap(),
+            user_date: None,
+            amount: Decimal::from_str(amount).unwrap(),
+            payee: payee.map(str::to_string),
+            normalized_payee: None,
+            transaction_type: "DEBIT".to_string(),
+            fitid: None,
+            check_number: None,
+            status: None,
+            memo: memo.map(str::to_string),
+            account_id: None,
+            currency: None,
+            original_currency: None,
+            original_amount: None,
+            raw_source: None,
+        }
+    }
+
+    #[test]
+    fn test_write_qif() {
+        let transactions = [
+            transaction("-50.00", Some("Coffee Shop"), Some("Morning\ncoffee")),
+            transaction("1500.00", None, None),
+        ];
+
+        assert_eq!(
+            write_qif(&transactions, QifAccountType::Bank),
+            "!Type:Bank\n\
+             D12/26/2025\nT-50.00\nPCoffee Shop\nMMorning coffee\n^\n\
+             D12/26/2025\nT1500.00\n^\n"
+        );
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let mut first = transaction("-50.00", Some("Coffee, Inc."), Some("Morning \"brew\""));
+        first.fitid = Some("202512260".to_string());
+        let transactions = [first, transaction("1500.00", None, None)];
+
+        let mut output = Vec::new();
+        write_csv(&transactions, &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "Date,Type,Description,Amount,FITID,Memo\n\
+             2025-12-26,DEBIT,\"Coffee, Inc.\",-50.00,202512260,\"Morning \"\"brew\"\"\"\n\
+             2025-12-26,DEBIT,,1500.00,,\n"
+        );
+    }
+
+    #[rstest]
+    #[case(QifAccountType::Bank, "!Type:Bank\n")]
+    #[case(QifAccountType::Cash, "!Type:Cash\n")]
+    #[case(QifAccountType::CreditCard, "!Type:CCard\n")]
+    #[case(QifAccountType::OtherAsset, "!Type:Oth A\n")]
+    #[case(QifAccountType::OtherLiability, "!Type:Oth L\n")]
+    fn test_write_qif_header(#[case] account_type: QifAccountType, #[case] expected: &str) {
+        assert_eq!(write_qif(&[], account_type), expected);
+    }
+}
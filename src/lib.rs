@@ -9,11 +9,25 @@
 //! ```
 
 mod builder;
+mod locale;
+#[cfg(feature = "mime")]
+mod mime;
 mod types;
 
+pub mod analysis;
+#[cfg(feature = "arrow")]
+pub mod columnar;
 pub mod errors;
+pub mod export;
 pub mod parsers;
+pub mod warnings;
 
-pub use builder::{FileFormat, ParsedTransaction, ParserBuilder};
+pub use builder::{
+    FileFormat, ParseReport, ParsedTransaction, ParserBuilder, ValidationReport, parse_qfx,
+};
+pub use errors::{RowError, StatementResult};
+pub use export::write_csv;
+pub use locale::NumberLocale;
 pub use parsers::prelude::*;
-pub use types::Transaction;
+pub use types::{SortOrder, Transaction, TransactionFilter, TransactionOverrides, TransactionType};
+pub use warnings::ParseWarning;
@@ -0,0 +1,144 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NumberLocale {
+    /// `1,234.56`, `$`
+    UsEnglish,
+    /// `1,234.56`, `£`
+    UkEnglish,
+    /// `1.234,56`, `R$`
+    BrazilianPortuguese,
+    /// `1.234,56`, `€`
+    GermanGerman,
+    /// `1 234,56`, `€`
+    FrenchFrench,
+    /// `1'234.56`, `CHF`
+    SwissGerman,
+    Custom {
+        decimal_separator: char,
+        grouping_separator: Option<char>,
+        currency_symbol: Option<String>,
+    },
+}
+
+impl NumberLocale {
+    pub fn decimal_separator(&self) -> char {
+        match self {
+            NumberLocale::UsEnglish | NumberLocale::UkEnglish | NumberLocale::SwissGerman => '.',
+            NumberLocale::BrazilianPortuguese
+            | NumberLocale::GermanGerman
+            | NumberLocale::FrenchFrench => ',',
+            NumberLocale::Custom {
+                decimal_separator, ..
+            } => *decimal_separator,
+        }
+    }
+
+    pub fn grouping_separators(&self) -> &[char] {
+        match self {
+            NumberLocale::UsEnglish | NumberLocale::UkEnglish => &[','],
+            NumberLocale::BrazilianPortuguese | NumberLocale::GermanGerman => &['.'],
+            NumberLocale::FrenchFrench => &[' ', '\u{00A0}', '\u{202F}'],
+            NumberLocale::SwissGerman => &['\'', '\u{2019}'],
+            NumberLocale::Custom {
+                grouping_separator, ..
+            } => grouping_separator.as_slice(),
+        }
+    }
+
+    pub fn currency_symbol(&self) -> Option<&str> {
+        match self {
+            NumberLocale::UsEnglish => Some("$"),
+            NumberLocale::UkEnglish => Some("£"),
+            NumberLocale::BrazilianPortuguese => Some("R$"),
+            NumberLocale::GermanGerman | NumberLocale::FrenchFrench => Some("€"),
+            NumberLocale::SwissGerman => Some("CHF"),
+            NumberLocale::Custom {
+                currency_symbol, ..
+            } => currency_symbol.as_deref(),
+        }
+    }
+
+    pub fn parse_amount(&self, raw: &str) -> Result<Decimal, String> {
+        let mut value = raw.trim();
+        let negative = value.starts_with('-');
+        if negative {
+            value = value[1..].trim_start();
+        }
+
+        if let Some(symbol) = self.currency_symbol() {
+            value = value
+                .strip_prefix(symbol)
+                .or_else(|| value.strip_suffix(symbol))
+                .unwrap_or(value)
+                .trim();
+        }
+
+        let decimal_separator = self.decimal_separator();
+        let grouping_separators = self.grouping_separators();
+        let mut normalized = String::with_capacity(value.len() + 1);
+        if negative {
+            normalized.push('-');
+        }
+        for c in value.chars() {
+            if c == decimal_separator {
+                normalized.push('.');
+            } else if !grouping_separators.contains(&c) {
+                normalized.push(c);
+            }
+        }
+
+        Decimal::from_str(&normalized).map_err(|e| format!("Invalid amount: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(NumberLocale::UsEnglish, "1,234.56", "1234.56")]
+    #[case(NumberLocale::UsEnglish, "$1,234.56", "1234.56")]
+    #[case(NumberLocale::UsEnglish, "-$50.00", "-50.00")]
+    #[case(NumberLocale::UkEnglish, "£1,000.00", "1000.00")]
+    #[case(NumberLocale::BrazilianPortuguese, "R$ 1.234,56", "1234.56")]
+    #[case(NumberLocale::BrazilianPortuguese, "-1.234,56", "-1234.56")]
+    #[case(NumberLocale::GermanGerman, "1.234,56 €", "1234.56")]
+    #[case(NumberLocale::FrenchFrench, "1 234,56", "1234.56")]
+    #[case(NumberLocale::FrenchFrench, "1\u{00A0}234,56 €", "1234.56")]
+    #[case(NumberLocale::SwissGerman, "1'234.56", "1234.56")]
+    #[case(NumberLocale::SwissGerman, "CHF 1\u{2019}234.56", "1234.56")]
+    #[case(NumberLocale::GermanGerman, "50", "50")]
+    fn test_parse_amount(#[case] locale: NumberLocale, #[case] raw: &str, #[case] expected: &str) {
+        assert_eq!(
+            locale.parse_amount(raw).unwrap(),
+            Decimal::from_str(expected).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_amount_custom() {
+        let locale = NumberLocale::Custom {
+            decimal_separator: ',',
+            grouping_separator: Some('_'),
+            currency_symbol: Some("kr".to_string()),
+        };
+
+        assert_eq!(
+            locale.parse_amount("1_234,56 kr").unwrap(),
+            Decimal::from_str("1234.56").unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case(NumberLocale::UsEnglish, "abc")]
+    #[case(NumberLocale::UsEnglish, "")]
+    #[case(NumberLocale::GermanGerman, "1,2,3")]
+    fn test_parse_amount_invalid(#[case] locale: NumberLocale, #[case] raw: &str) {
+        let result = locale.parse_amount(raw);
+        assert!(result.unwrap_err().contains("Invalid amount"));
+    }
+}
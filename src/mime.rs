@@ -0,0 +1,218 @@
+use base64::{Engine, engine::general_purpose::STANDARD};
+
+const OFX_CONTENT_TYPES: &[&str] = &[
+    "application/x-ofx",
+    "application/ofx",
+    "application/vnd.intu.qfx",
+];
+
+type Headers = Vec<(String, String)>;
+
+/// Returns the decoded OFX attachment of a multipart MIME message.
+///
+/// A part counts as OFX when its content type is an OFX type or its file
+/// name ends in `.ofx`/`.qfx`. Nested multiparts are searched too. Returns
+/// `None` when `content` is not multipart or has no OFX part.
+pub(crate) fn extract_ofx_part(content: &str) -> Option<String> {
+    let (headers, body) = split_part(content)?;
+    let boundary = header_param(&headers, "content-type", "boundary")?;
+    let delimiter = format!("--{}", boundary);
+
+    body.split(delimiter.as_str()).skip(1).find_map(|part| {
+        let part = part.trim_start_matches(['\r', '\n']);
+        if let Some(ofx) = extract_ofx_part(part) {
+            return Some(ofx);
+        }
+
+        let (headers, body) = split_part(part)?;
+        if is_ofx_part(&headers) {
+            decode_body(&headers, body)
+        } else {
+            None
+        }
+    })
+}
+
+fn split_part(part: &str) -> Option<(Headers, &str)> {
+    let (index, separator_len) = ["\r\n\r\n", "\n\n"]
+        .iter()
+        .filter_map(|separator| part.find(separator).map(|i| (i, separator.len())))
+        .min()?;
+
+    let mut headers: Headers = Vec::new();
+    for line in part[..index].lines() {
+        if line.starts_with([' ', '\t']) {
+            if let Some((_, value)) = headers.last_mut() {
+                value.push(' ');
+                value.push_str(line.trim());
+            }
+        } else if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+        }
+    }
+
+    Some((headers, &part[index + separator_len..]))
+}
+
+fn header<'a>(headers: &'a Headers, name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(header_name, _)| header_name == name)
+        .map(|(_, value)| value.as_str())
+}
+
+fn header_param(headers: &Headers, name: &str, param: &str) -> Option<String> {
+    header(headers, name)?.split(';').skip(1).find_map(|pair| {
+        let (key, value) = pair.split_once('=')?;
+        key.trim()
+            .eq_ignore_ascii_case(param)
+            .then(|| value.trim().trim_matches('"').to_string())
+    })
+}
+
+fn is_ofx_part(headers: &Headers) -> bool {
+    let content_type = header(headers, "content-type")
+        .and_then(|value| value.split(';').next())
+        .map(|value| value.trim().to_ascii_lowercase());
+    if content_type.is_some_and(|value| OFX_CONTENT_TYPES.contains(&value.as_str())) {
+        return true;
+    }
+
+    [
+        header_param(headers, "content-disposition", "filename"),
+        header_param(headers, "content-type", "name"),
+    ]
+    .into_iter()
+    .flatten()
+    .any(|name| {
+        let name = name.to_ascii_lowercase();
+        name.ends_with(".ofx") || name.ends_with(".qfx")
+    })
+}
+
+fn decode_body(headers: &Headers, body: &str) -> Option<String> {
+    let encoding = header(headers, "content-transfer-encoding")
+        .map(|value| value.to_ascii_lowercase())
+        .unwrap_or_default();
+
+    match encoding.as_str() {
+        "base64" => {
+            let compact: String = body.chars().filter(|c| !c.is_whitespace()).collect();
+            String::from_utf8(STANDARD.decode(compact).ok()?).ok()
+        }
+        "quoted-printable" => String::from_utf8(decode_quoted_printable(body)).ok(),
+        _ => Some(body.to_string()),
+    }
+}
+
+fn decode_quoted_printable(body: &str) -> Vec<u8> {
+    let bytes = body.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'=' {
+            decoded.push(bytes[i]);
+            i += 1;
+        } else if bytes[i + 1..].starts_with(b"\r\n") {
+            i += 3;
+        } else if bytes[i + 1..].starts_with(b"\n") {
+            i += 2;
+        } else if let Some(byte) = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            decoded.push(byte);
+            i += 3;
+        } else {
+            decoded.push(b'=');
+            i += 1;
+        }
+    }
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const OFX: &str = "OFXHEADER:100\n\n<OFX>\n<BANKMSGSRSV1>\n</BANKMSGSRSV1>\n</OFX>\n";
+
+    fn message(part_headers: &str, part_body: &str) -> String {
+        format!(
+            "From: bank@example.com\r\n\
+             Content-Type: multipart/mixed; boundary=\"XYZ\"\r\n\
+             \r\n\
+             --XYZ\r\n\
+             Content-Type: text/plain\r\n\
+             \r\n\
+             Your statement is attached.\r\n\
+             --XYZ\r\n\
+             {}\r\n\
+             \r\n\
+             {}\r\n\
+             --XYZ--\r\n",
+            part_headers, part_body
+        )
+    }
+
+    #[rstest]
+    #[case("Content-Type: application/x-ofx")]
+    #[case("Content-Type: APPLICATION/OFX; charset=us-ascii")]
+    #[case(
+        "Content-Type: application/octet-stream\r\nContent-Disposition: attachment; filename=\"stmt.QFX\""
+    )]
+    fn test_extract_ofx_part_plain(#[case] part_headers: &str) {
+        let extracted = extract_ofx_part(&message(part_headers, OFX)).unwrap();
+        assert!(extracted.starts_with("OFXHEADER:100"));
+        assert!(extracted.contains("</OFX>"));
+    }
+
+    #[test]
+    fn test_extract_ofx_part_base64() {
+        let encoded = STANDARD.encode(OFX);
+        let (head, tail) = encoded.split_at(20);
+        let body = format!("{}\r\n{}", head, tail);
+
+        let extracted = extract_ofx_part(&message(
+            "Content-Type: application/x-ofx\r\nContent-Transfer-Encoding: base64",
+            &body,
+        ))
+        .unwrap();
+        assert_eq!(extracted, OFX);
+    }
+
+    #[test]
+    fn test_extract_ofx_part_quoted_printable() {
+        let extracted = extract_ofx_part(&message(
+            "Content-Type: application/x-ofx\r\nContent-Transfer-Encoding: quoted-printable",
+            "<OFX>=3D<NAME>Caf=C3=A9 =\r\nShop</OFX>",
+        ))
+        .unwrap();
+        assert!(extracted.starts_with("<OFX>=<NAME>Café Shop</OFX>"));
+    }
+
+    #[test]
+    fn test_extract_ofx_part_nested_multipart() {
+        let inner = message("Content-Type: application/x-ofx", OFX).replace("XYZ", "INNER");
+        let (inner_headers, inner_body) = inner.split_once("\r\n\r\n").unwrap();
+        let inner_headers = inner_headers.replace("From: bank@example.com\r\n", "");
+
+        let extracted = extract_ofx_part(&message(&inner_headers, inner_body)).unwrap();
+        assert!(extracted.contains("<OFX>"));
+    }
+
+    #[rstest]
+    #[case(OFX)]
+    #[case("Content-Type: text/plain\r\n\r\nhello")]
+    fn test_extract_ofx_part_not_multipart(#[case] content: &str) {
+        assert_eq!(extract_ofx_part(content), None);
+    }
+
+    #[test]
+    fn test_extract_ofx_part_without_ofx_attachment() {
+        let content = message("Content-Type: application/pdf", "%PDF-1.4");
+        assert_eq!(extract_ofx_part(&content), None);
+    }
+}
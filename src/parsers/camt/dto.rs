@@ -0,0 +1,185 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Debug, Deserialize)]
+pub(super) struct CamtDocument {
+    #[serde(rename = "BkToCstmrStmt")]
+    pub(super) statement_message: CamtStatementMessage,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct CamtStatementMessage {
+    #[serde(rename = "Stmt", default)]
+    pub(super) statements: Vec<CamtStatement>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct CamtStatement {
+    #[serde(rename = "Acct", default)]
+    pub(super) account: Option<CamtAccount>,
+    #[serde(rename = "Ntry", default)]
+    pub(super) entries: Vec<CamtEntryRaw>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct CamtAccount {
+    #[serde(rename = "Id", default)]
+    id: Option<CamtAccountId>,
+}
+
+impl CamtAccount {
+    /// The IBAN, else the proprietary `Othr/Id`.
+    pub(super) fn identifier(&self) -> Option<&str> {
+        let id = self.id.as_ref()?;
+        id.iban
+            .as_deref()
+            .or_else(|| id.other.as_ref().and_then(|other| other.id.as_deref()))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CamtAccountId {
+    #[serde(rename = "IBAN", default)]
+    iban: Option<String>,
+    #[serde(rename = "Othr", default)]
+    other: Option<CamtOtherId>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CamtOtherId {
+    #[serde(rename = "Id", default)]
+    id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct CamtEntryRaw {
+    #[serde(rename = "NtryRef", default)]
+    entry_ref: Option<String>,
+    #[serde(rename = "Amt")]
+    amount: CamtAmount,
+    #[serde(rename = "CdtDbtInd")]
+    credit_debit: String,
+    #[serde(rename = "BookgDt", default)]
+    booking_date: Option<CamtDate>,
+    #[serde(rename = "ValDt", default)]
+    value_date: Option<CamtDate>,
+    #[serde(rename = "AcctSvcrRef", default)]
+    servicer_ref: Option<String>,
+    #[serde(rename = "NtryDtls", default)]
+    details: Option<CamtEntryDetails>,
+    #[serde(rename = "AddtlNtryInf", default)]
+    additional_info: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CamtAmount {
+    #[serde(rename = "@Ccy", default)]
+    currency: Option<String>,
+    #[serde(rename = "#text")]
+    value: String,
+}
+
+/// `BookgDt`/`ValDt` hold either a `Dt` or a `DtTm`.
+#[derive(Debug, Deserialize)]
+struct CamtDate {
+    #[serde(rename = "Dt", default)]
+    date: Option<String>,
+    #[serde(rename = "DtTm", default)]
+    date_time: Option<String>,
+}
+
+impl CamtDate {
+    fn into_string(self) -> Option<String> {
+        self.date.or(self.date_time)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CamtEntryDetails {
+    #[serde(rename = "TxDtls", default)]
+    transactions: Vec<CamtTransactionDetails>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CamtTransactionDetails {
+    #[serde(rename = "RmtInf", default)]
+    remittance: Option<CamtRemittance>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CamtRemittance {
+    #[serde(rename = "Ustrd", default)]
+    unstructured: Vec<String>,
+}
+
+/// One `<Ntry>` of a camt.053 statement.
+///
+/// `amount` is unsigned as in the file; `credit_debit` (`CRDT`/`DBIT`)
+/// gives the direction. Dates are kept as written, either `YYYY-MM-DD` or
+/// an ISO date-time.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CamtTransaction {
+    pub amount: Decimal,
+    pub currency: Option<String>,
+    pub credit_debit: String,
+    pub booking_date: String,
+    pub value_date: Option<String>,
+    /// The `Ustrd` remittance lines joined with spaces, else `AddtlNtryInf`.
+    pub description: Option<String>,
+    /// `AcctSvcrRef`, else `NtryRef`.
+    pub reference: Option<String>,
+    pub account_id: Option<String>,
+}
+
+impl CamtTransaction {
+    pub fn is_debit(&self) -> bool {
+        self.credit_debit.trim().eq_ignore_ascii_case("DBIT")
+    }
+
+    pub(super) fn from_raw(raw: CamtEntryRaw, account_id: Option<&str>) -> Result<Self, String> {
+        let amount = Decimal::from_str(raw.amount.value.trim())
+            .map_err(|e| format!("Invalid amount: {}", e))?;
+        let booking_date = raw
+            .booking_date
+            .and_then(CamtDate::into_string)
+            .ok_or("Missing booking date")?;
+        let credit_debit = raw.credit_debit.trim().to_uppercase();
+        if credit_debit != "CRDT" && credit_debit != "DBIT" {
+            return Err(format!(
+                "Invalid credit/debit indicator: {:?}",
+                raw.credit_debit
+            ));
+        }
+
+        let remittance: Vec<String> = raw
+            .details
+            .into_iter()
+            .flat_map(|details| details.transactions)
+            .filter_map(|transaction| transaction.remittance)
+            .flat_map(|remittance| remittance.unstructured)
+            .map(|line| line.trim().to_string())
+            .filter(|line| !line.is_empty())
+            .collect();
+        let description = if remittance.is_empty() {
+            non_empty(raw.additional_info)
+        } else {
+            Some(remittance.join(" "))
+        };
+
+        Ok(CamtTransaction {
+            amount,
+            currency: non_empty(raw.amount.currency),
+            credit_debit,
+            booking_date: booking_date.trim().to_string(),
+            value_date: raw.value_date.and_then(CamtDate::into_string),
+            description,
+            reference: non_empty(raw.servicer_ref).or_else(|| non_empty(raw.entry_ref)),
+            account_id: account_id.map(str::to_string),
+        })
+    }
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|value| !value.trim().is_empty())
+}
@@ -0,0 +1,232 @@
+use super::dto::{CamtDocument, CamtTransaction};
+use crate::{errors::RowError, parsers::traits::Parser};
+
+const ISO20022_NAMESPACE: &str = "urn:iso:std:iso:20022";
+
+/// ISO 20022 camt.053 bank-to-customer statements.
+pub struct Camt053Parser;
+
+impl Parser for Camt053Parser {
+    type Output = CamtTransaction;
+
+    fn is_supported(_filename: Option<&str>, content: &str) -> bool {
+        content.contains(ISO20022_NAMESPACE) || content.contains("<BkToCstmrStmt>")
+    }
+
+    /// Full confidence when both the ISO 20022 namespace and the
+    /// `<BkToCstmrStmt>` element are present, partial for either alone.
+    fn confidence(_filename: Option<&str>, content: &str) -> f32 {
+        match (
+            content.contains(ISO20022_NAMESPACE),
+            content.contains("<BkToCstmrStmt>"),
+        ) {
+            (true, true) => 1.0,
+            (true, false) | (false, true) => 0.6,
+            (false, false) => 0.0,
+        }
+    }
+
+    fn parse(content: &str) -> Result<Vec<Self::Output>, String> {
+        Self::parse_rows(content)?
+            .into_iter()
+            .map(|row| row.map_err(|error| format!("Ntry {}: {}", error.row, error.message)))
+            .collect()
+    }
+}
+
+impl Camt053Parser {
+    /// Every `<Ntry>` in file order, with the ones that failed to convert
+    /// as a [`RowError`] instead of failing the whole document. Rows are
+    /// numbered across all statements, from one.
+    pub fn parse_rows(content: &str) -> Result<Vec<Result<CamtTransaction, RowError>>, String> {
+        let document: CamtDocument =
+            serde_xml_rs::from_str(content).map_err(|e| format!("XML parse error: {}", e))?;
+
+        let mut rows = Vec::new();
+        for statement in document.statement_message.statements {
+            let account_id = statement
+                .account
+                .as_ref()
+                .and_then(|acct| acct.identifier());
+            for entry in statement.entries {
+                let row = rows.len() + 1;
+                rows.push(
+                    CamtTransaction::from_raw(entry, account_id)
+                        .map_err(|message| RowError { row, message }),
+                );
+            }
+        }
+        Ok(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    const SAMPLE_CAMT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.053.001.02">
+    <BkToCstmrStmt>
+        <GrpHdr>
+            <MsgId>MSG-1</MsgId>
+        </GrpHdr>
+        <Stmt>
+            <Id>STMT-1</Id>
+            <Acct>
+                <Id>
+                    <IBAN>DE89370400440532013000</IBAN>
+                </Id>
+                <Ccy>EUR</Ccy>
+            </Acct>
+            <Ntry>
+                <NtryRef>1</NtryRef>
+                <Amt Ccy="EUR">50.00</Amt>
+                <CdtDbtInd>DBIT</CdtDbtInd>
+                <Sts>BOOK</Sts>
+                <BookgDt>
+                    <Dt>2025-12-26</Dt>
+                </BookgDt>
+                <ValDt>
+                    <Dt>2025-12-27</Dt>
+                </ValDt>
+                <AcctSvcrRef>REF-001</AcctSvcrRef>
+                <NtryDtls>
+                    <TxDtls>
+                        <RmtInf>
+                            <Ustrd>Coffee Shop</Ustrd>
+                            <Ustrd>Morning coffee</Ustrd>
+                        </RmtInf>
+                    </TxDtls>
+                </NtryDtls>
+            </Ntry>
+            <Ntry>
+                <NtryRef>2</NtryRef>
+                <Amt Ccy="EUR">1500.00</Amt>
+                <CdtDbtInd>CRDT</CdtDbtInd>
+                <BookgDt>
+                    <DtTm>2025-12-28T09:30:00</DtTm>
+                </BookgDt>
+                <AddtlNtryInf>ACME Corp payroll</AddtlNtryInf>
+            </Ntry>
+        </Stmt>
+    </BkToCstmrStmt>
+</Document>"#;
+
+    #[test]
+    fn test_parse_entries() {
+        let transactions = Camt053Parser::parse(SAMPLE_CAMT).unwrap();
+        assert_eq!(transactions.len(), 2);
+
+        let debit = &transactions[0];
+        assert_eq!(debit.amount, Decimal::from_str("50.00").unwrap());
+        assert!(debit.is_debit());
+        assert_eq!(debit.currency.as_deref(), Some("EUR"));
+        assert_eq!(debit.booking_date, "2025-12-26");
+        assert_eq!(debit.value_date.as_deref(), Some("2025-12-27"));
+        assert_eq!(
+            debit.description.as_deref(),
+            Some("Coffee Shop Morning coffee")
+        );
+        assert_eq!(debit.reference.as_deref(), Some("REF-001"));
+        assert_eq!(debit.account_id.as_deref(), Some("DE89370400440532013000"));
+
+        let credit = &transactions[1];
+        assert!(!credit.is_debit());
+        assert_eq!(credit.booking_date, "2025-12-28T09:30:00");
+        assert_eq!(credit.description.as_deref(), Some("ACME Corp payroll"));
+        assert_eq!(credit.reference.as_deref(), Some("2"));
+    }
+
+    #[test]
+    fn test_parse_missing_booking_date() {
+        let content = SAMPLE_CAMT.replacen("<Dt>2025-12-26</Dt>", "", 1);
+        let result = Camt053Parser::parse(&content);
+        assert_eq!(result.unwrap_err(), "Ntry 1: Missing booking date");
+    }
+
+    #[test]
+    fn test_parse_invalid_amount() {
+        let content = SAMPLE_CAMT.replacen("50.00", "abc", 1);
+        let result = Camt053Parser::parse(&content);
+        assert!(result.unwrap_err().starts_with("Ntry 1: Invalid amount"));
+    }
+
+    #[rstest]
+    #[case("<CdtDbtInd>DBIT</CdtDbtInd>", "<CdtDbtInd>DEBIT</CdtDbtInd>")]
+    #[case("<CdtDbtInd>DBIT</CdtDbtInd>", "<CdtDbtInd></CdtDbtInd>")]
+    fn test_parse_invalid_credit_debit(#[case] from: &str, #[case] to: &str) {
+        let content = SAMPLE_CAMT.replacen(from, to, 1);
+        let result = Camt053Parser::parse(&content);
+        assert!(
+            result
+                .unwrap_err()
+                .starts_with("Ntry 1: Invalid credit/debit indicator")
+        );
+    }
+
+    #[test]
+    fn test_parse_lowercase_credit_debit() {
+        let content = SAMPLE_CAMT.replacen("<CdtDbtInd>DBIT", "<CdtDbtInd>dbit", 1);
+        let transactions = Camt053Parser::parse(&content).unwrap();
+        assert_eq!(transactions[0].credit_debit, "DBIT");
+    }
+
+    #[test]
+    fn test_parse_no_entries() {
+        let content = "<Document><BkToCstmrStmt><Stmt><Id>1</Id></Stmt></BkToCstmrStmt></Document>";
+        let transactions = Camt053Parser::parse(content).unwrap();
+        assert!(transactions.is_empty());
+    }
+
+    #[test]
+    fn test_parse_rows_collects_row_errors() {
+        let content = SAMPLE_CAMT.replacen("50.00", "abc", 1);
+
+        let rows = Camt053Parser::parse_rows(&content).unwrap();
+        assert_eq!(rows.len(), 2);
+        let error = rows[0].as_ref().unwrap_err();
+        assert_eq!(error.row, 1);
+        assert!(error.message.starts_with("Invalid amount"));
+        assert_eq!(rows[1].as_ref().unwrap().amount, Decimal::from(1500));
+    }
+
+    #[test]
+    fn test_parse_rows_numbered_across_statements() {
+        let statement = &SAMPLE_CAMT[SAMPLE_CAMT.find("<Stmt>").unwrap()
+            ..SAMPLE_CAMT.find("</Stmt>").unwrap() + "</Stmt>".len()];
+        let content = SAMPLE_CAMT.replacen(
+            "</Stmt>",
+            &format!("</Stmt>{}", statement.replacen("1500.00", "oops", 1)),
+            1,
+        );
+
+        let result = Camt053Parser::parse(&content);
+        assert!(result.unwrap_err().starts_with("Ntry 4: Invalid amount"));
+    }
+
+    #[test]
+    fn test_parse_invalid_xml() {
+        let result = Camt053Parser::parse("<Document><BkToCstmrStmt>");
+        assert!(result.unwrap_err().contains("XML parse error"));
+    }
+
+    #[rstest]
+    #[case(SAMPLE_CAMT, true)]
+    #[case("<Document><BkToCstmrStmt></BkToCstmrStmt></Document>", true)]
+    #[case("<OFX></OFX>", false)]
+    #[case("date,amount", false)]
+    fn test_is_supported(#[case] content: &str, #[case] expected: bool) {
+        assert_eq!(Camt053Parser::is_supported(None, content), expected);
+    }
+
+    #[rstest]
+    #[case(SAMPLE_CAMT, 1.0)]
+    #[case("<Document><BkToCstmrStmt></BkToCstmrStmt></Document>", 0.6)]
+    #[case("<OFX></OFX>", 0.0)]
+    fn test_confidence(#[case] content: &str, #[case] expected: f32) {
+        assert_eq!(Camt053Parser::confidence(None, content), expected);
+    }
+}
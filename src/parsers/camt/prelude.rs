@@ -0,0 +1,2 @@
+pub use super::dto::CamtTransaction;
+pub use super::parser::Camt053Parser;
@@ -1,3 +1,4 @@
+pub mod camt;
 pub mod prelude;
 pub mod qfx;
 pub mod traits;
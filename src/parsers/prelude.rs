@@ -1,2 +1,3 @@
+pub use super::camt::prelude::*;
 pub use super::qfx::prelude::*;
-pub use super::traits::Parser;
+pub use super::traits::{DynParser, Parser};
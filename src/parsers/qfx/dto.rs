@@ -1,65 +1,233 @@
 use rust_decimal::Decimal;
-use serde::{Deserialize, Serialize};
+use serde::{
+    Deserialize, Deserializer, Serialize,
+    de::{self, MapAccess, Visitor},
+};
+use std::fmt;
 
-use super::types::QfxDate;
+use super::{options::QfxOptions, types::QfxDate};
+use crate::errors::RowError;
 
 #[derive(Debug, Deserialize)]
 pub(super) struct QfxBankMsgsRsV1 {
-    #[serde(rename = "STMTTRNRS")]
-    pub(super) stmt_trn_rs: QfxStmtTrnRs,
+    #[serde(rename = "STMTTRNRS", default)]
+    pub(super) stmt_trn_rs: Vec<QfxStmtTrnRs>,
 }
 
 #[derive(Debug, Deserialize)]
 pub(super) struct QfxCreditCardMsgsRsV1 {
-    #[serde(rename = "CCSTMTTRNRS")]
-    pub(super) cc_stmt_trn_rs: QfxCcStmtTrnRs,
+    #[serde(rename = "CCSTMTTRNRS", default)]
+    pub(super) cc_stmt_trn_rs: Vec<QfxCcStmtTrnRs>,
 }
 
 #[derive(Debug, Deserialize)]
 pub(super) struct QfxStmtTrnRs {
-    #[serde(rename = "STMTRS")]
-    pub(super) stmt_rs: QfxStmtRs,
+    #[serde(rename = "STATUS", default)]
+    pub(super) status: Option<QfxStatus>,
+    #[serde(rename = "STMTRS", default)]
+    pub(super) stmt_rs: Option<QfxStmtRs>,
+    /// Some non-conforming exports drop the `STMTRS` wrapper and put
+    /// `BANKTRANLIST` directly under `STMTTRNRS`.
+    #[serde(rename = "BANKTRANLIST", default)]
+    pub(super) bank_transaction_lists: Vec<QfxBankTransactionList>,
 }
 
 #[derive(Debug, Deserialize)]
 pub(super) struct QfxCcStmtTrnRs {
-    #[serde(rename = "CCSTMTRS")]
-    pub(super) cc_stmt_rs: QfxCcStmtRs,
+    #[serde(rename = "STATUS", default)]
+    pub(super) status: Option<QfxStatus>,
+    #[serde(rename = "CCSTMTRS", default)]
+    pub(super) cc_stmt_rs: Option<QfxCcStmtRs>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct QfxStatus {
+    #[serde(rename = "CODE")]
+    pub(super) code: String,
+    #[serde(rename = "SEVERITY")]
+    pub(super) severity: String,
+    #[serde(rename = "MESSAGE", default)]
+    pub(super) message: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 pub(super) struct QfxStmtRs {
-    #[serde(rename = "BANKTRANLIST")]
-    pub(super) bank_transaction_list: QfxBankTransactionList,
+    #[serde(rename = "CURDEF", default)]
+    pub(super) currency: Option<String>,
+    #[serde(rename = "BANKACCTFROM", default)]
+    pub(super) bank_acct_from: Option<QfxAcctFrom>,
+    #[serde(rename = "BANKTRANLIST", default)]
+    pub(super) bank_transaction_lists: Vec<QfxBankTransactionList>,
+    #[serde(rename = "LEDGERBAL", default)]
+    pub(super) ledger_balance: Option<QfxBalance>,
 }
 
 #[derive(Debug, Deserialize)]
 pub(super) struct QfxCcStmtRs {
-    #[serde(rename = "BANKTRANLIST")]
-    pub(super) bank_transaction_list: QfxBankTransactionList,
+    #[serde(rename = "CURDEF", default)]
+    pub(super) currency: Option<String>,
+    #[serde(rename = "CCACCTFROM", default)]
+    pub(super) cc_acct_from: Option<QfxAcctFrom>,
+    #[serde(rename = "BANKTRANLIST", default)]
+    pub(super) bank_transaction_lists: Vec<QfxBankTransactionList>,
+    #[serde(rename = "LEDGERBAL", default)]
+    pub(super) ledger_balance: Option<QfxBalance>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct QfxBalance {
+    #[serde(rename = "BALAMT", default)]
+    pub(super) amount: Option<String>,
+    #[serde(rename = "DTASOF", default)]
+    pub(super) as_of: Option<QfxDate>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct QfxAcctFrom {
+    #[serde(rename = "ACCTID")]
+    pub(super) acct_id: String,
 }
 
 #[derive(Debug, Deserialize)]
 pub(super) struct QfxBankTransactionList {
+    /// Declared number of `STMTTRN`s, sent by some banks so truncated
+    /// downloads can be detected.
+    #[serde(rename = "TRNCOUNT", default)]
+    pub(super) declared_count: Option<String>,
     #[serde(rename = "STMTTRN", default)]
     pub(super) transactions: Vec<QfxTransactionRaw>,
 }
 
+/// Microsoft Money exports can nest the statement response inside the
+/// signon message set, next to `SONRS`, instead of in its own message set.
+#[derive(Debug, Deserialize)]
+pub(super) struct QfxSignonMsgsRsV1 {
+    #[serde(rename = "SONRS", default)]
+    pub(super) sonrs: Option<QfxSonRs>,
+    #[serde(rename = "STMTTRNRS", default)]
+    pub(super) stmt_trn_rs: Option<QfxStmtTrnRs>,
+    #[serde(rename = "CCSTMTTRNRS", default)]
+    pub(super) cc_stmt_trn_rs: Option<QfxCcStmtTrnRs>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct QfxSonRs {
+    #[serde(rename = "LANGUAGE", default)]
+    pub(super) language: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub(super) struct OfxXml {
+    #[serde(rename = "SIGNONMSGSRSV1", default)]
+    pub(super) signon_msgs: Option<QfxSignonMsgsRsV1>,
     #[serde(rename = "BANKMSGSRSV1")]
     pub(super) bank_msgs: Option<QfxBankMsgsRsV1>,
     #[serde(rename = "CREDITCARDMSGSRSV1")]
     pub(super) cc_msgs: Option<QfxCreditCardMsgsRsV1>,
 }
 
+pub(super) struct QfxStatementData {
+    pub(super) status: Option<QfxStatus>,
+    /// The signon `LANGUAGE`, shared by every statement in the document.
+    pub(super) language: Option<String>,
+    pub(super) account_id: Option<String>,
+    pub(super) currency: Option<String>,
+    pub(super) transaction_lists: Option<Vec<QfxBankTransactionList>>,
+    pub(super) ledger_balance: Option<QfxBalance>,
+}
+
+impl From<QfxStmtTrnRs> for QfxStatementData {
+    fn from(stmt_trn_rs: QfxStmtTrnRs) -> Self {
+        let (account_id, currency, transaction_lists, ledger_balance) = match stmt_trn_rs.stmt_rs {
+            Some(s) => (
+                s.bank_acct_from.map(|a| a.acct_id),
+                s.currency,
+                Some(s.bank_transaction_lists),
+                s.ledger_balance,
+            ),
+            None if !stmt_trn_rs.bank_transaction_lists.is_empty() => {
+                (None, None, Some(stmt_trn_rs.bank_transaction_lists), None)
+            }
+            None => (None, None, None, None),
+        };
+        QfxStatementData {
+            status: stmt_trn_rs.status,
+            language: None,
+            account_id,
+            currency: non_empty(currency),
+            transaction_lists,
+            ledger_balance,
+        }
+    }
+}
+
+impl From<QfxCcStmtTrnRs> for QfxStatementData {
+    fn from(cc_stmt_trn_rs: QfxCcStmtTrnRs) -> Self {
+        let (account_id, currency, transaction_lists, ledger_balance) =
+            match cc_stmt_trn_rs.cc_stmt_rs {
+                Some(s) => (
+                    s.cc_acct_from.map(|a| a.acct_id),
+                    s.currency,
+                    Some(s.bank_transaction_lists),
+                    s.ledger_balance,
+                ),
+                None => (None, None, None, None),
+            };
+        QfxStatementData {
+            status: cc_stmt_trn_rs.status,
+            language: None,
+            account_id,
+            currency: non_empty(currency),
+            transaction_lists,
+            ledger_balance,
+        }
+    }
+}
+
+impl OfxXml {
+    /// Every bank and credit card statement in the document, in order. The
+    /// signon fallback is only used when neither message set has any.
+    pub(super) fn into_statements(self) -> Vec<QfxStatementData> {
+        let language = self
+            .signon_msgs
+            .as_ref()
+            .and_then(|signon| signon.sonrs.as_ref())
+            .and_then(|sonrs| non_empty(sonrs.language.clone()));
+        let bank = self.bank_msgs.into_iter().flat_map(|b| b.stmt_trn_rs);
+        let cc = self.cc_msgs.into_iter().flat_map(|c| c.cc_stmt_trn_rs);
+        let mut statements: Vec<QfxStatementData> = bank
+            .map(QfxStatementData::from)
+            .chain(cc.map(QfxStatementData::from))
+            .collect();
+
+        if statements.is_empty()
+            && let Some(signon_msgs) = self.signon_msgs
+        {
+            statements.extend(
+                signon_msgs
+                    .stmt_trn_rs
+                    .map(QfxStatementData::from)
+                    .into_iter()
+                    .chain(signon_msgs.cc_stmt_trn_rs.map(QfxStatementData::from)),
+            );
+        }
+
+        for statement in &mut statements {
+            statement.language = language.clone();
+        }
+        statements
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub(super) struct QfxTransactionRaw {
     #[serde(rename = "TRNTYPE")]
     trn_type: String,
     #[serde(rename = "DTPOSTED")]
     dt_posted: QfxDate,
-    #[serde(rename = "TRNAMT")]
+    #[serde(rename = "DTUSER", default)]
+    dt_user: Option<QfxDate>,
+    #[serde(rename = "TRNAMT", deserialize_with = "deserialize_amount", default)]
     amount: String,
     #[serde(rename = "FITID", default)]
     fitid: Option<String>,
@@ -67,14 +235,75 @@ pub(super) struct QfxTransactionRaw {
     name: Option<String>,
     #[serde(rename = "MEMO", default)]
     memo: Option<String>,
+    #[serde(rename = "CHECKNUM", default)]
+    check_num: Option<String>,
+    #[serde(rename = "REFNUM", default)]
+    ref_num: Option<String>,
+    #[serde(rename = "CURRENCY", default)]
+    currency: Option<QfxCurrency>,
+    #[serde(rename = "ORIGCURRENCY", default)]
+    orig_currency: Option<QfxCurrency>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Deserialize)]
+struct QfxCurrency {
+    #[serde(rename = "CURRATE", default)]
+    rate: Option<String>,
+    #[serde(rename = "CURSYM", default)]
+    symbol: Option<String>,
+}
+
+/// Accepts both `<TRNAMT>-50.00</TRNAMT>` and the nested
+/// `<TRNAMT><AMOUNT>-50.00</AMOUNT><CURSYM>USD</CURSYM></TRNAMT>` form.
+fn deserialize_amount<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct AmountVisitor;
+
+    impl<'de> Visitor<'de> for AmountVisitor {
+        type Value = String;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("an amount string or an element with an AMOUNT child")
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+            Ok(value.to_string())
+        }
+
+        fn visit_string<E: de::Error>(self, value: String) -> Result<Self::Value, E> {
+            Ok(value)
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            let mut amount = None;
+            while let Some(key) = map.next_key::<String>()? {
+                if key == "AMOUNT" || key == "#text" {
+                    amount = Some(map.next_value::<String>()?);
+                } else {
+                    map.next_value::<de::IgnoredAny>()?;
+                }
+            }
+            Ok(amount.unwrap_or_default())
+        }
+    }
+
+    // serde-xml-rs does not support `deserialize_any`; as a map, a flat leaf
+    // surfaces its text under the `#text` key.
+    deserializer.deserialize_map(AmountVisitor)
+}
+
+/// Equality and hashing cover every field; dates compare as written, so
+/// `20251226` and `20251226000000` differ.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct QfxTransaction {
     #[serde(rename = "TRNTYPE")]
     pub trn_type: String,
     #[serde(rename = "DTPOSTED")]
     pub dt_posted: QfxDate,
+    #[serde(rename = "DTUSER", default)]
+    pub dt_user: Option<QfxDate>,
     #[serde(rename = "TRNAMT")]
     pub amount: Decimal,
     #[serde(rename = "FITID")]
@@ -83,18 +312,189 @@ pub struct QfxTransaction {
     pub name: Option<String>,
     #[serde(rename = "MEMO")]
     pub memo: Option<String>,
+    #[serde(rename = "CHECKNUM", default)]
+    pub check_num: Option<String>,
+    #[serde(rename = "REFNUM", default)]
+    pub ref_num: Option<String>,
+    #[serde(rename = "ACCTID", default)]
+    pub account_id: Option<String>,
+    /// The transaction's own `CURRENCY`, else the statement's `CURDEF`.
+    #[serde(rename = "CURRENCY", default)]
+    pub currency: Option<String>,
+    #[serde(skip)]
+    pub original_currency: Option<String>,
+    #[serde(skip)]
+    pub original_amount: Option<Decimal>,
+    #[serde(skip)]
+    pub raw_source: Option<String>,
+}
+
+/// Result of [`QfxParser::parse_statement`](super::parser::QfxParser::parse_statement):
+/// the transactions plus the statement's closing `LEDGERBAL`, language and
+/// default currency, if present. For multi-account files the metadata is the
+/// first statement's.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QfxStatement {
+    pub transactions: Vec<QfxTransaction>,
+    pub ledger_balance: Option<Decimal>,
+    pub balance_date: Option<QfxDate>,
+    /// `LANGUAGE` from the signon response, e.g. `ENG`.
+    #[serde(default)]
+    pub language: Option<String>,
+    /// The statement's `CURDEF`.
+    #[serde(default)]
+    pub default_currency: Option<String>,
+    /// Only filled when `QfxOptions::collect_row_errors` is set.
+    #[serde(default)]
+    pub row_errors: Vec<RowError>,
+}
+
+/// Empty elements such as `<MEMO/>` or `<MEMO></MEMO>` mean "no value".
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|value| !value.trim().is_empty())
+}
+
+/// Removes all Unicode whitespace plus zero-width characters, which creep
+/// into amounts and dates through copy-paste.
+fn strip_invisible(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| {
+            !c.is_whitespace() && !matches!(c, '\u{200B}'..='\u{200D}' | '\u{2060}' | '\u{FEFF}')
+        })
+        .collect()
+}
+
+/// Splits a trailing `CR` (credit) or `DR` (debit) marker, as in `50.00CR`,
+/// off an amount. The flag is `Some(true)` for a debit and `None` when
+/// there is no marker.
+fn split_cr_dr(text: &str) -> (&str, Option<bool>) {
+    let suffix = text.len().checked_sub(2).and_then(|at| text.get(at..));
+    match suffix {
+        Some(marker) if marker.eq_ignore_ascii_case("CR") => (&text[..text.len() - 2], Some(false)),
+        Some(marker) if marker.eq_ignore_ascii_case("DR") => (&text[..text.len() - 2], Some(true)),
+        _ => (text, None),
+    }
+}
+
+fn parse_amount(text: &str, options: &QfxOptions) -> Result<Decimal, String> {
+    use std::str::FromStr;
+    let (number, is_debit) = split_cr_dr(text);
+    let amount = match &options.number_locale {
+        Some(locale) => locale.parse_amount(number)?,
+        None => Decimal::from_str(number).map_err(|e| format!("Invalid amount: {}", e))?,
+    };
+    Ok(match is_debit {
+        Some(true) => -amount.abs(),
+        Some(false) => amount.abs(),
+        None => amount,
+    })
+}
+
+/// Parses `BALAMT` with the same rules as `TRNAMT`. Empty means no balance.
+pub(super) fn parse_balance(
+    amount: Option<String>,
+    options: &QfxOptions,
+) -> Result<Option<Decimal>, String> {
+    non_empty(amount.as_deref().map(strip_invisible))
+        .map(|amount| parse_amount(&amount, options))
+        .transpose()
+}
+
+fn parse_rate(rate: Option<String>) -> Result<Option<Decimal>, String> {
+    use std::str::FromStr;
+    non_empty(rate)
+        .map(|rate| {
+            Decimal::from_str(rate.trim()).map_err(|e| format!("Invalid currency rate: {}", e))
+        })
+        .transpose()
 }
 
 impl QfxTransaction {
-    pub(super) fn from_raw(raw: QfxTransactionRaw) -> Result<Self, String> {
-        use std::str::FromStr;
+    /// `default_currency` is the statement's `CURDEF`, used when the
+    /// transaction has no `CURRENCY` of its own.
+    pub(super) fn from_raw(
+        raw: QfxTransactionRaw,
+        options: &QfxOptions,
+        default_currency: Option<&str>,
+    ) -> Result<Self, String> {
+        let raw_amount = strip_invisible(&raw.amount);
+        let mut amount = if raw_amount.is_empty() && options.amount_from_memo.is_some() {
+            Decimal::ZERO
+        } else {
+            parse_amount(&raw_amount, options)?
+        };
+
+        if amount.is_zero()
+            && let Some(regex) = &options.amount_from_memo
+            && let Some(found) = [&raw.memo, &raw.name]
+                .into_iter()
+                .flatten()
+                .find_map(|text| regex.captures(text))
+        {
+            let matched = found.get(1).or_else(|| found.get(0)).unwrap();
+            amount = parse_amount(&strip_invisible(matched.as_str()), options)?;
+        }
+
+        let (mut original_currency, mut original_amount) = match raw.orig_currency {
+            Some(orig) => {
+                let rate = parse_rate(orig.rate)?;
+                // CURRATE converts the original currency into CURDEF, which
+                // TRNAMT is already expressed in.
+                let original_amount = rate
+                    .filter(|rate| !rate.is_zero())
+                    .map(|rate| {
+                        let mut original = amount
+                            .checked_div(rate)
+                            .ok_or("Currency rate overflows the amount")?;
+                        original.rescale(amount.scale());
+                        Ok::<_, String>(original)
+                    })
+                    .transpose()?;
+                (non_empty(orig.symbol), original_amount)
+            }
+            None => (None, None),
+        };
+
+        let (mut currency, rate) = match raw.currency {
+            Some(currency) => (non_empty(currency.symbol), parse_rate(currency.rate)?),
+            None => (None, None),
+        };
+
+        // CURRATE converts TRNAMT from CURSYM into CURDEF.
+        if options.apply_ofx_rates
+            && let Some(rate) = rate
+            && default_currency.is_some()
+            && currency.is_some()
+        {
+            original_currency = currency.take();
+            original_amount = Some(amount);
+            let scale = amount.scale();
+            amount = amount
+                .checked_mul(rate)
+                .ok_or("Currency rate overflows the amount")?;
+            amount.rescale(scale);
+        }
+        let currency = currency.or_else(|| default_currency.map(str::to_string));
+
         Ok(QfxTransaction {
-            trn_type: raw.trn_type,
-            dt_posted: raw.dt_posted,
-            amount: Decimal::from_str(&raw.amount).map_err(|e| format!("Invalid amount: {}", e))?,
-            fitid: raw.fitid,
-            name: raw.name,
-            memo: raw.memo,
+            trn_type: strip_invisible(&raw.trn_type),
+            dt_posted: strip_invisible(raw.dt_posted.as_str()).into(),
+            dt_user: raw
+                .dt_user
+                .map(|date| QfxDate::from(strip_invisible(date.as_str())))
+                .filter(|date| !date.as_str().is_empty()),
+            amount,
+            fitid: non_empty(raw.fitid),
+            name: non_empty(raw.name),
+            memo: non_empty(raw.memo),
+            check_num: non_empty(raw.check_num),
+            ref_num: non_empty(raw.ref_num),
+            account_id: None,
+            currency,
+            original_currency,
+            original_amount,
+            raw_source: None,
         })
     }
 }
@@ -102,6 +502,7 @@ impl QfxTransaction {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::locale::NumberLocale;
     use rstest::rstest;
     use std::str::FromStr;
 
@@ -109,17 +510,22 @@ mod tests {
         QfxTransactionRaw {
             trn_type: "DEBIT".to_string(),
             dt_posted: "20251226120000".into(),
+            dt_user: None,
             amount: amount.to_string(),
             fitid: Some("202512260".to_string()),
             name: Some("Test Payee".to_string()),
             memo: Some("Test memo".to_string()),
+            check_num: None,
+            ref_num: None,
+            currency: None,
+            orig_currency: None,
         }
     }
 
     #[test]
     fn test_from_raw_valid_positive_amount() {
         let raw = create_test_raw_transaction("1500.00");
-        let result = QfxTransaction::from_raw(raw);
+        let result = QfxTransaction::from_raw(raw, &QfxOptions::default(), None);
 
         assert!(result.is_ok());
         let transaction = result.unwrap();
@@ -133,7 +539,7 @@ mod tests {
     #[test]
     fn test_from_raw_valid_negative_amount() {
         let raw = create_test_raw_transaction("-50.00");
-        let result = QfxTransaction::from_raw(raw);
+        let result = QfxTransaction::from_raw(raw, &QfxOptions::default(), None);
 
         assert!(result.is_ok());
         let transaction = result.unwrap();
@@ -149,36 +555,100 @@ mod tests {
     #[case("0.01")]
     fn test_from_raw_various_valid_amounts(#[case] amount: &str) {
         let raw = create_test_raw_transaction(amount);
-        let result = QfxTransaction::from_raw(raw);
+        let result = QfxTransaction::from_raw(raw, &QfxOptions::default(), None);
         assert!(result.is_ok());
         assert_eq!(result.unwrap().amount, Decimal::from_str(amount).unwrap());
     }
 
+    #[rstest]
+    #[case("-50.00\u{00A0}", "-50.00")]
+    #[case("\u{FEFF}-50.00", "-50.00")]
+    #[case("-\u{00A0}50.00", "-50.00")]
+    #[case("1\u{200B}500.00", "1500.00")]
+    fn test_from_raw_strips_invisible_characters(#[case] amount: &str, #[case] expected: &str) {
+        let raw = create_test_raw_transaction(amount);
+        let result = QfxTransaction::from_raw(raw, &QfxOptions::default(), None);
+        assert_eq!(result.unwrap().amount, Decimal::from_str(expected).unwrap());
+    }
+
+    #[rstest]
+    #[case("50.00CR", "50.00")]
+    #[case("50.00DR", "-50.00")]
+    #[case("50.00 dr", "-50.00")]
+    #[case("-50.00CR", "50.00")]
+    #[case("1500.00", "1500.00")]
+    fn test_from_raw_cr_dr_suffix(#[case] amount: &str, #[case] expected: &str) {
+        let raw = create_test_raw_transaction(amount);
+        let result = QfxTransaction::from_raw(raw, &QfxOptions::default(), None);
+        assert_eq!(result.unwrap().amount, Decimal::from_str(expected).unwrap());
+    }
+
+    #[test]
+    fn test_from_raw_strips_invisible_characters_from_date_and_type() {
+        let raw = QfxTransactionRaw {
+            trn_type: "\u{200B}DEBIT\u{00A0}".to_string(),
+            dt_posted: "\u{FEFF}20251226120000".into(),
+            dt_user: Some("\u{00A0}".into()),
+            ..create_test_raw_transaction("-50.00")
+        };
+
+        let transaction = QfxTransaction::from_raw(raw, &QfxOptions::default(), None).unwrap();
+        assert_eq!(transaction.trn_type, "DEBIT");
+        assert_eq!(transaction.dt_posted.as_str(), "20251226120000");
+        assert!(transaction.dt_user.is_none());
+    }
+
     #[rstest]
     #[case("invalid")]
     #[case("abc")]
     #[case("$100.00")]
     #[case("")]
     #[case("1,000.00")]
+    #[case("CR")]
+    #[case("50.00CRDR")]
     fn test_from_raw_invalid_amounts(#[case] amount: &str) {
         let raw = create_test_raw_transaction(amount);
-        let result = QfxTransaction::from_raw(raw);
+        let result = QfxTransaction::from_raw(raw, &QfxOptions::default(), None);
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("Invalid amount"));
     }
 
+    #[rstest]
+    #[case(NumberLocale::BrazilianPortuguese, "-1.234,56", "-1234.56")]
+    #[case(NumberLocale::GermanGerman, "50,00", "50.00")]
+    #[case(NumberLocale::UsEnglish, "1,000.00", "1000.00")]
+    fn test_from_raw_with_number_locale(
+        #[case] locale: NumberLocale,
+        #[case] amount: &str,
+        #[case] expected: &str,
+    ) {
+        let raw = create_test_raw_transaction(amount);
+        let options = QfxOptions {
+            number_locale: Some(locale),
+            ..QfxOptions::default()
+        };
+
+        let result = QfxTransaction::from_raw(raw, &options, None);
+        assert_eq!(result.unwrap().amount, Decimal::from_str(expected).unwrap());
+    }
+
     #[test]
     fn test_from_raw_minimal_fields() {
         let raw = QfxTransactionRaw {
             trn_type: "CREDIT".to_string(),
             dt_posted: "20251225000000".into(),
+            dt_user: None,
             amount: "1500.00".to_string(),
             fitid: None,
             name: None,
             memo: None,
+            check_num: None,
+            ref_num: None,
+            currency: None,
+            orig_currency: None,
         };
 
-        let result = QfxTransaction::from_raw(raw);
+        let result = QfxTransaction::from_raw(raw, &QfxOptions::default(), None);
         assert!(result.is_ok());
 
         let transaction = result.unwrap();
@@ -194,19 +664,30 @@ mod tests {
         let transaction = QfxTransaction {
             trn_type: "DEBIT".to_string(),
             dt_posted: "20251226120000".into(),
+            dt_user: None,
             amount: Decimal::from_str("-50.00").unwrap(),
             fitid: Some("202512260".to_string()),
             name: Some("Test Payee".to_string()),
             memo: Some("Test memo".to_string()),
+            check_num: None,
+            ref_num: None,
+            account_id: None,
+            currency: Some("EUR".to_string()),
+            original_currency: None,
+            original_amount: None,
+            raw_source: None,
         };
 
         let json = serde_json::to_string(&transaction).unwrap();
         assert!(json.contains("DEBIT"));
         assert!(json.contains("Test Payee"));
+        assert!(json.contains(r#""CURRENCY":"EUR""#));
+        assert!(!json.contains("CURDEF"));
 
         let deserialized: QfxTransaction = serde_json::from_str(&json).unwrap();
         assert_eq!(deserialized.trn_type, transaction.trn_type);
         assert_eq!(deserialized.amount, transaction.amount);
         assert_eq!(deserialized.name, transaction.name);
+        assert_eq!(deserialized.currency, transaction.currency);
     }
 }
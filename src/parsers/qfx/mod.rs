@@ -1,4 +1,5 @@
 pub mod dto;
+pub mod options;
 pub mod parser;
 pub mod prelude;
 pub mod types;
@@ -0,0 +1,15 @@
+use crate::locale::NumberLocale;
+use regex::Regex;
+
+#[derive(Debug, Clone, Default)]
+pub struct QfxOptions {
+    pub keep_raw_source: bool,
+    pub number_locale: Option<NumberLocale>,
+    pub apply_ofx_rates: bool,
+    /// Fallback for exports that leave `TRNAMT` zero or missing and put the
+    /// amount in `MEMO`/`NAME`. See `ParserBuilder::amount_from_memo_regex`.
+    pub amount_from_memo: Option<Regex>,
+    /// Record transactions that fail to parse in `QfxStatement::row_errors`
+    /// instead of failing the whole file.
+    pub collect_row_errors: bool,
+}
@@ -1,8 +1,115 @@
-use super::dto::{OfxXml, QfxTransaction};
-use crate::parsers::traits::Parser;
+use super::dto::{
+    OfxXml, QfxBankTransactionList, QfxStatement, QfxStatus, QfxTransaction, parse_balance,
+};
+use super::options::QfxOptions;
+use crate::{errors::RowError, parsers::traits::Parser, warnings::ParseWarning};
+use std::io::{BufRead, BufReader, Read};
 
 pub struct QfxParser;
 
+impl QfxParser {
+    /// A transaction that fails to convert fails the parse with
+    /// `STMTTRN {row}: {message}`, `row` being its 1-based number across
+    /// all statements, unless `collect_row_errors` is set.
+    pub fn parse_with_options(
+        content: &str,
+        options: &QfxOptions,
+    ) -> Result<Vec<QfxTransaction>, String> {
+        Self::parse_with_warnings(content, options, &mut |_| {})
+    }
+
+    /// Like [`QfxParser::parse_with_options`], calling `on_warning` for each
+    /// non-fatal issue as it is found.
+    pub fn parse_with_warnings(
+        content: &str,
+        options: &QfxOptions,
+        on_warning: &mut dyn FnMut(&ParseWarning),
+    ) -> Result<Vec<QfxTransaction>, String> {
+        Self::parse_statement_with_warnings(content, options, on_warning)
+            .map(|statement| statement.transactions)
+    }
+
+    /// Like `parse`, but also returns the statement's ledger balance
+    /// (`LEDGERBAL`), e.g. for reconciling against the transactions.
+    pub fn parse_statement(content: &str) -> Result<QfxStatement, String> {
+        Self::parse_statement_with_warnings(content, &QfxOptions::default(), &mut |_| {})
+    }
+
+    /// The OFX section runs from the first `<OFX>` to the last `</OFX>`, so
+    /// a `</OFX>` inside a `MEMO` does not cut the statement short. This
+    /// needs the whole input in memory; [`QfxParser::parse_statement_reader`]
+    /// avoids that at the cost of a stricter rule for the closing tag.
+    pub fn parse_statement_with_warnings(
+        content: &str,
+        options: &QfxOptions,
+        on_warning: &mut dyn FnMut(&ParseWarning),
+    ) -> Result<QfxStatement, String> {
+        let content = skip_preamble(content);
+        let ofx_start = content.find("<OFX>").ok_or("Missing <OFX> tag")?;
+        let ofx_end = content.rfind("</OFX>").ok_or("Missing </OFX> tag")? + "</OFX>".len();
+        if ofx_end <= ofx_start {
+            return Err("</OFX> tag appears before <OFX>".to_string());
+        }
+
+        let is_xml = content.starts_with("<?xml");
+        parse_section(&content[ofx_start..ofx_end], is_xml, options, on_warning)
+    }
+
+    /// Like [`QfxParser::parse_statement_with_warnings`], reading from
+    /// `reader` and buffering only the OFX section.
+    ///
+    /// The header before `<OFX>` is read line by line and discarded, and
+    /// reading stops at the closing `</OFX>`, so trailing data is never
+    /// read. Unlike the `&str` entry points, which can look for the last
+    /// `</OFX>`, the reader cannot see ahead: it takes the first `</OFX>`
+    /// that starts a line or directly follows another tag. A `</OFX>`
+    /// inside element text, such as a `MEMO`, does not end the section.
+    pub fn parse_statement_reader<R: Read>(
+        reader: R,
+        options: &QfxOptions,
+        on_warning: &mut dyn FnMut(&ParseWarning),
+    ) -> Result<QfxStatement, String> {
+        let mut is_xml = false;
+        let mut section = String::new();
+        let mut in_ofx = false;
+        let mut closed = false;
+
+        for line in BufReader::new(reader).lines() {
+            let line = line.map_err(|e| format!("Read error: {}", e))?;
+            let mut rest = line.as_str();
+
+            if !in_ofx {
+                if rest.trim_start().starts_with("<?xml") {
+                    is_xml = true;
+                }
+                match rest.find("<OFX>") {
+                    Some(start) => {
+                        in_ofx = true;
+                        rest = &rest[start..];
+                    }
+                    None => continue,
+                }
+            }
+
+            if let Some(end) = closing_tag_end(rest) {
+                section.push_str(&rest[..end]);
+                closed = true;
+                break;
+            }
+            section.push_str(rest);
+            section.push('\n');
+        }
+
+        if !in_ofx {
+            return Err("Missing <OFX> tag".to_string());
+        }
+        if !closed {
+            return Err("Missing </OFX> tag".to_string());
+        }
+        parse_section(&section, is_xml, options, on_warning)
+    }
+}
+
 impl Parser for QfxParser {
     type Output = QfxTransaction;
 
@@ -20,48 +127,265 @@ impl Parser for QfxParser {
             || trimmed.contains("DATA:OFXSGML")
     }
 
-    fn parse(content: &str) -> Result<Vec<Self::Output>, String> {
-        let xml_content = if content.trim().starts_with("<?xml") {
-            content.to_string()
+    /// Full confidence for a `.qfx`/`.ofx` name, an SGML header or an
+    /// `<OFX>` root element; less when the markers only appear somewhere
+    /// inside the content.
+    fn confidence(filename: Option<&str>, content: &str) -> f32 {
+        if let Some(name) = filename {
+            let ext = name.to_lowercase();
+            if ext.ends_with(".qfx") || ext.ends_with(".ofx") {
+                return 1.0;
+            }
+        }
+
+        if skip_preamble(content).starts_with("OFXHEADER:")
+            || first_element(content).is_some_and(|element| element.starts_with("<OFX>"))
+        {
+            1.0
+        } else if content.contains("OFXHEADER:") || content.contains("DATA:OFXSGML") {
+            0.8
+        } else if content.contains("<OFX>") {
+            0.5
         } else {
-            convert_sgml_to_xml(content)?
+            0.0
+        }
+    }
+
+    fn parse(content: &str) -> Result<Vec<Self::Output>, String> {
+        Self::parse_with_options(content, &QfxOptions::default())
+    }
+
+    /// Reads OFX from `reader`, buffering only the OFX section; see
+    /// [`QfxParser::parse_statement_reader`].
+    fn parse_reader<R: Read>(reader: R) -> Result<Vec<QfxTransaction>, String> {
+        Self::parse_statement_reader(reader, &QfxOptions::default(), &mut |_| {})
+            .map(|statement| statement.transactions)
+    }
+}
+
+/// End of the `</OFX>` in `line` that closes the document: the first one
+/// at the start of the line or right after another tag's `>`.
+fn closing_tag_end(line: &str) -> Option<usize> {
+    line.match_indices("</OFX>")
+        .find(|(start, _)| {
+            let before = line[..*start].trim_end();
+            before.is_empty() || before.ends_with('>')
+        })
+        .map(|(start, tag)| start + tag.len())
+}
+
+/// Parses an `<OFX>...</OFX>` section, converting it from SGML first
+/// unless the document declared itself XML.
+fn parse_section(
+    section: &str,
+    is_xml: bool,
+    options: &QfxOptions,
+    on_warning: &mut dyn FnMut(&ParseWarning),
+) -> Result<QfxStatement, String> {
+    if is_xml {
+        return parse_ofx_section(section, options, on_warning);
+    }
+    #[cfg(feature = "tracing")]
+    tracing::trace!(bytes = section.len(), "converting OFX SGML to XML");
+    parse_ofx_section(&convert_sgml_to_xml(section)?, options, on_warning)
+}
+
+/// Skips anything, such as saved HTTP response headers, before the first
+/// `OFXHEADER:`, `<?xml` or `<OFX>` marker.
+fn skip_preamble(content: &str) -> &str {
+    ["OFXHEADER:", "<?xml", "<OFX>"]
+        .iter()
+        .filter_map(|marker| content.find(marker))
+        .min()
+        .map_or(content, |start| &content[start..])
+}
+
+/// The rest of `content` from its first tag that is not a `<?...?>`
+/// processing instruction or a `<!...>` comment/declaration.
+fn first_element(content: &str) -> Option<&str> {
+    let mut rest = content;
+    loop {
+        let start = rest.find('<')?;
+        rest = &rest[start..];
+        if !(rest.starts_with("<?") || rest.starts_with("<!")) {
+            return Some(rest);
+        }
+        rest = &rest[1..];
+    }
+}
+
+fn parse_ofx_section(
+    ofx_content: &str,
+    options: &QfxOptions,
+    on_warning: &mut dyn FnMut(&ParseWarning),
+) -> Result<QfxStatement, String> {
+    let ofx: OfxXml =
+        serde_xml_rs::from_str(ofx_content).map_err(|e| format!("XML parse error: {}", e))?;
+
+    let statements = ofx.into_statements();
+    let mut statement_sources = if options.keep_raw_source {
+        statement_blocks(ofx_content)
+    } else {
+        Vec::new()
+    }
+    .into_iter();
+
+    // The balance reported is the first statement's; multi-account files
+    // are flattened into a single transaction list.
+    let mut found = false;
+    let mut transactions = Vec::new();
+    let mut row_errors = Vec::new();
+    let mut first_balance = None;
+    let mut language = None;
+    let mut default_currency = None;
+    for statement in statements {
+        let mut raw_sources = statement_sources
+            .next()
+            .map(extract_stmttrn_blocks)
+            .unwrap_or_default()
+            .into_iter();
+        check_status(statement.status.as_ref(), on_warning)?;
+        let Some(transaction_lists) = statement.transaction_lists else {
+            continue;
         };
+        if !found {
+            first_balance = statement.ledger_balance;
+            language = statement.language;
+            default_currency = statement.currency.clone();
+            found = true;
+        }
+        for list in transaction_lists {
+            check_transaction_count(&list, on_warning);
+            for raw in list.transactions {
+                let row = transactions.len() + row_errors.len() + 1;
+                let raw_source = raw_sources.next();
+                match QfxTransaction::from_raw(raw, options, statement.currency.as_deref()) {
+                    Ok(txn) => transactions.push(QfxTransaction {
+                        account_id: statement.account_id.clone(),
+                        raw_source,
+                        ..txn
+                    }),
+                    Err(message) if options.collect_row_errors => {
+                        row_errors.push(RowError { row, message })
+                    }
+                    Err(message) => return Err(format!("STMTTRN {row}: {message}")),
+                }
+            }
+        }
+    }
+    if !found {
+        return Err("No transaction data found".to_string());
+    }
+
+    let (ledger_balance, balance_date) = match first_balance {
+        Some(balance) => (parse_balance(balance.amount, options)?, balance.as_of),
+        None => (None, None),
+    };
+
+    Ok(QfxStatement {
+        transactions,
+        ledger_balance,
+        balance_date,
+        language,
+        default_currency,
+        row_errors,
+    })
+}
 
-        let ofx_start = xml_content.find("<OFX>").ok_or("Missing <OFX> tag")?;
-        let ofx_end = xml_content.find("</OFX>").ok_or("Missing </OFX> tag")?;
-        let ofx_content = &xml_content[ofx_start..=ofx_end + 5];
-
-        let ofx: OfxXml =
-            serde_xml_rs::from_str(ofx_content).map_err(|e| format!("XML parse error: {}", e))?;
-
-        let raw_transactions = ofx
-            .bank_msgs
-            .map(|b| b.stmt_trn_rs.stmt_rs.bank_transaction_list.transactions)
-            .or_else(|| {
-                ofx.cc_msgs.map(|c| {
-                    c.cc_stmt_trn_rs
-                        .cc_stmt_rs
-                        .bank_transaction_list
-                        .transactions
-                })
-            })
-            .ok_or("No transaction data found")?;
-
-        raw_transactions
-            .into_iter()
-            .map(QfxTransaction::from_raw)
-            .collect()
+/// Warns when a list's `TRNCOUNT` disagrees with its `STMTTRN`s, which
+/// usually means a truncated download. An unreadable `TRNCOUNT` is ignored.
+fn check_transaction_count(
+    list: &QfxBankTransactionList,
+    on_warning: &mut dyn FnMut(&ParseWarning),
+) {
+    let declared = list
+        .declared_count
+        .as_deref()
+        .and_then(|count| count.trim().parse::<usize>().ok());
+    if let Some(declared) = declared
+        && declared != list.transactions.len()
+    {
+        on_warning(&ParseWarning::TransactionCountMismatch {
+            declared,
+            actual: list.transactions.len(),
+        });
     }
 }
 
-fn convert_sgml_to_xml(content: &str) -> Result<String, String> {
-    const LEAF_ELEMENTS: &[&str] = &[
-        "CODE", "SEVERITY", "MESSAGE", "DTSERVER", "LANGUAGE", "ORG", "FID", "TRNUID", "CURDEF",
-        "BANKID", "ACCTID", "ACCTTYPE", "DTSTART", "DTEND", "TRNTYPE", "DTPOSTED", "DTUSER",
-        "TRNAMT", "FITID", "NAME", "MEMO", "INTU.BID", "DTPROFUP", "DTASOF", "BALAMT",
-    ];
+/// Fails on `ERROR` severity. `WARN`, or `INFO` with a non-zero code, is
+/// reported through `on_warning` and parsing continues.
+fn check_status(
+    status: Option<&QfxStatus>,
+    on_warning: &mut dyn FnMut(&ParseWarning),
+) -> Result<(), String> {
+    let Some(status) = status else {
+        return Ok(());
+    };
+
+    let code = status.code.trim();
+    match status.severity.trim().to_uppercase().as_str() {
+        "ERROR" => Err(format!(
+            "OFX status error {}: {}",
+            code,
+            status.message.as_deref().unwrap_or("no message")
+        )),
+        severity if severity == "WARN" || code != "0" => {
+            on_warning(&ParseWarning::OfxStatus {
+                code: code.to_string(),
+                message: status.message.clone(),
+            });
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// The `<STMTTRNRS>` and `<CCSTMTTRNRS>` blocks of `ofx_content`, in the
+/// order `OfxXml::into_statements` returns their statements, so raw
+/// sources are matched within the right statement.
+fn statement_blocks(ofx_content: &str) -> Vec<&str> {
+    let within = |message_set: &str, tag: &str| {
+        element_blocks(ofx_content, message_set)
+            .first()
+            .map_or_else(Vec::new, |set| element_blocks(set, tag))
+    };
+
+    let mut blocks = within("BANKMSGSRSV1", "STMTTRNRS");
+    blocks.extend(within("CREDITCARDMSGSRSV1", "CCSTMTTRNRS"));
+    if blocks.is_empty() {
+        blocks.extend(within("SIGNONMSGSRSV1", "STMTTRNRS").into_iter().take(1));
+        blocks.extend(within("SIGNONMSGSRSV1", "CCSTMTTRNRS").into_iter().take(1));
+    }
+    blocks
+}
+
+fn extract_stmttrn_blocks(ofx_content: &str) -> Vec<String> {
+    element_blocks(ofx_content, "STMTTRN")
+        .into_iter()
+        .map(str::to_string)
+        .collect()
+}
+
+/// Every `<tag>...</tag>` in `content`, in document order.
+fn element_blocks<'a>(content: &'a str, tag: &str) -> Vec<&'a str> {
+    let open = format!("<{tag}>");
+    let close = format!("</{tag}>");
+
+    let mut blocks = Vec::new();
+    let mut rest = content;
+    while let Some(start) = rest.find(&open) {
+        let Some(len) = rest[start..].find(&close) else {
+            break;
+        };
+        let end = start + len + close.len();
+        blocks.push(&rest[start..end]);
+        rest = &rest[end..];
+    }
+    blocks
+}
 
-    let mut result = String::new();
+fn convert_sgml_to_xml(content: &str) -> Result<String, String> {
+    let mut result = String::with_capacity(content.len() + content.len() / 4);
     let mut lines = content.lines().peekable();
 
     while let Some(line) = lines.peek() {
@@ -71,54 +395,136 @@ fn convert_sgml_to_xml(content: &str) -> Result<String, String> {
         lines.next();
     }
 
-    for line in lines {
-        let trimmed = line.trim();
-        if trimmed.is_empty() {
-            continue;
-        }
+    let lines: Vec<&str> = lines
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    for (i, line) in lines.iter().enumerate() {
+        convert_sgml_line(line, lines.get(i + 1).copied(), &mut result);
+    }
+
+    Ok(result)
+}
 
-        if !trimmed.starts_with('<') || trimmed.starts_with("</") {
-            result.push_str(trimmed);
+const LEAF_ELEMENTS: &[&str] = &[
+    "CODE", "SEVERITY", "MESSAGE", "DTSERVER", "LANGUAGE", "ORG", "FID", "TRNUID", "CURDEF",
+    "BANKID", "ACCTID", "ACCTTYPE", "DTSTART", "DTEND", "TRNTYPE", "DTPOSTED", "DTUSER", "TRNAMT",
+    "FITID", "NAME", "MEMO", "INTU.BID", "DTPROFUP", "DTASOF", "BALAMT", "CURRATE", "CURSYM",
+    "TRNCOUNT", "CHECKNUM", "REFNUM",
+];
+
+fn convert_sgml_line(trimmed: &str, next_line: Option<&str>, result: &mut String) {
+    if !trimmed.starts_with('<') || trimmed.starts_with("</") {
+        result.push_str(trimmed);
+        result.push('\n');
+        return;
+    }
+
+    let tag_end = trimmed
+        .find(|c: char| c == '>' || c.is_whitespace())
+        .unwrap_or(trimmed.len());
+    let tag_name = &trimmed[1..tag_end];
+
+    if let Some(content_start) = trimmed.find('>') {
+        let after_tag = &trimmed[content_start + 1..];
+        let value = after_tag.trim_start();
+
+        // `<TRNAMT>` can wrap `<AMOUNT>` and `<CURSYM>` instead of holding
+        // the amount, so it is only a leaf when no child follows.
+        if tag_name.eq_ignore_ascii_case("TRNAMT") && has_amount_child(value, next_line) {
+            result.push_str(&trimmed[..content_start + 1]);
             result.push('\n');
-            continue;
+            if !value.is_empty() {
+                convert_sgml_line(value, next_line, result);
+            }
+            return;
         }
 
-        let tag_end = trimmed
-            .find(|c: char| c == '>' || c.is_whitespace())
-            .unwrap_or(trimmed.len());
-        let tag_name = &trimmed[1..tag_end];
-
-        if LEAF_ELEMENTS.contains(&tag_name.to_uppercase().as_str()) {
-            if let Some(content_start) = trimmed.find('>') {
-                let after_tag = &trimmed[content_start + 1..];
-                let closing_tag = format!("</{}>", tag_name);
-
-                if !after_tag.contains(&closing_tag) {
-                    let content_end = after_tag.find("</").unwrap_or(after_tag.len());
-                    let content = after_tag[..content_end].trim();
-                    let trailing = &after_tag[content_end..];
-
-                    result.push_str(&trimmed[..content_start + 1]);
-                    result.push_str(content);
-                    result.push_str(&closing_tag);
-                    result.push_str(trailing);
-                    result.push('\n');
-                    continue;
-                }
-            }
+        // Unlisted tags, e.g. vendor extensions like `<INTU.XYZ>`, count
+        // as leaves when a value follows them on the same line.
+        let has_inline_value = !value.is_empty() && !value.starts_with('<');
+        let is_leaf = has_inline_value
+            || LEAF_ELEMENTS
+                .iter()
+                .any(|leaf| leaf.eq_ignore_ascii_case(tag_name));
+
+        if is_leaf && !has_closing_tag(after_tag, tag_name) {
+            // Only closing tags at the end of the line, as in
+            // `<SEVERITY>INFO</STATUS>`, end the value; a `</OFX>` inside
+            // a memo is text.
+            let content_end = trailing_closing_tags(after_tag);
+            let content = after_tag[..content_end].trim();
+            let trailing = &after_tag[content_end..];
+
+            result.push_str(&trimmed[..content_start + 1]);
+            result.push_str(&content.replace('<', "&lt;"));
+            result.push_str("</");
+            result.push_str(tag_name);
+            result.push('>');
+            result.push_str(trailing);
+            result.push('\n');
+            return;
         }
+    }
 
-        result.push_str(trimmed);
-        result.push('\n');
+    result.push_str(trimmed);
+    result.push('\n');
+}
+
+/// Whether a `TRNAMT` whose text is `value` opens an element on the same
+/// line or is followed by `<AMOUNT>` on the next.
+fn has_amount_child(value: &str, next_line: Option<&str>) -> bool {
+    if value.is_empty() {
+        next_line
+            .and_then(|line| line.get(..8))
+            .is_some_and(|tag| tag.eq_ignore_ascii_case("<AMOUNT>"))
+    } else {
+        value.starts_with('<') && !value.starts_with("</")
     }
+}
 
-    Ok(result)
+/// Start of the run of closing tags that ends `text`, or its length when
+/// it does not end with one.
+fn trailing_closing_tags(text: &str) -> usize {
+    text.match_indices("</")
+        .map(|(i, _)| i)
+        .find(|&i| is_closing_tags(&text[i..]))
+        .unwrap_or(text.len())
+}
+
+fn is_closing_tags(text: &str) -> bool {
+    let mut rest = text.trim();
+    while !rest.is_empty() {
+        let Some(tag) = rest.strip_prefix("</") else {
+            return false;
+        };
+        let Some(end) = tag.find('>') else {
+            return false;
+        };
+        let name = &tag[..end];
+        if name.is_empty() || name.contains(|c: char| c.is_whitespace() || c == '<') {
+            return false;
+        }
+        rest = tag[end + 1..].trim_start();
+    }
+    true
+}
+
+fn has_closing_tag(text: &str, tag_name: &str) -> bool {
+    text.match_indices("</").any(|(i, _)| {
+        text[i + 2..]
+            .strip_prefix(tag_name)
+            .is_some_and(|rest| rest.starts_with('>'))
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::NaiveDate;
     use rstest::rstest;
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
 
     const SAMPLE_XML_QFX: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
 <OFX>
@@ -186,6 +592,9 @@ VERSION:102
 </BANKMSGSRSV1>
 </OFX>"#;
 
+    const HTTP_PREAMBLE: &str =
+        "HTTP/1.1 200 OK\r\nContent-Type: application/x-ofx\r\nContent-Length: 1234\r\n\r\n";
+
     // Test is_supported method
     #[rstest]
     #[case(Some("test.qfx"), "", true)]
@@ -205,6 +614,83 @@ VERSION:102
         assert_eq!(QfxParser::is_supported(filename, content), expected);
     }
 
+    #[rstest]
+    #[case(Some("test.qfx"), "", 1.0)]
+    #[case(None, SAMPLE_XML_QFX, 1.0)]
+    #[case(None, SAMPLE_SGML_QFX, 1.0)]
+    #[case(None, "HTTP/1.1 200 OK\n\nOFXHEADER:100", 1.0)]
+    #[case(None, "note: DATA:OFXSGML", 0.8)]
+    #[case(None, "<Document><Ustrd><![CDATA[<OFX>]]></Ustrd></Document>", 0.5)]
+    #[case(None, "random content", 0.0)]
+    fn test_confidence(
+        #[case] filename: Option<&str>,
+        #[case] content: &str,
+        #[case] expected: f32,
+    ) {
+        assert_eq!(QfxParser::confidence(filename, content), expected);
+    }
+
+    #[rstest]
+    #[case(SAMPLE_XML_QFX)]
+    #[case(SAMPLE_SGML_QFX)]
+    fn test_is_supported_with_http_preamble(#[case] sample: &str) {
+        let content = format!("{}{}", HTTP_PREAMBLE, sample);
+        assert!(QfxParser::is_supported(None, &content));
+    }
+
+    #[rstest]
+    #[case(SAMPLE_XML_QFX)]
+    #[case(SAMPLE_SGML_QFX)]
+    fn test_parse_with_http_preamble(#[case] sample: &str) {
+        let content = format!("{}{}", HTTP_PREAMBLE, sample);
+
+        let transactions = QfxParser::parse(&content).unwrap();
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].amount.to_string(), "-50.00");
+    }
+
+    #[test]
+    fn test_parse_reader_with_http_preamble() {
+        let content = format!("{}{}", HTTP_PREAMBLE, SAMPLE_XML_QFX);
+
+        let transactions = QfxParser::parse_reader(content.as_bytes()).unwrap();
+        assert_eq!(transactions.len(), 1);
+    }
+
+    #[rstest]
+    #[case(SAMPLE_XML_QFX)]
+    #[case(SAMPLE_SGML_QFX)]
+    fn test_parse_with_trailing_null_bytes(#[case] sample: &str) {
+        let content = format!("{}\n\0\0\0", sample);
+
+        let transactions = QfxParser::parse(&content).unwrap();
+        assert_eq!(transactions.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_ends_exactly_at_closing_tag() {
+        let content = SAMPLE_XML_QFX.trim_end();
+        assert!(content.ends_with("</OFX>"));
+
+        let transactions = QfxParser::parse(content).unwrap();
+        assert_eq!(transactions.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_closing_tag_before_opening_tag() {
+        let result = QfxParser::parse("<?xml version=\"1.0\"?></OFX><OFX>");
+        assert_eq!(result.unwrap_err(), "</OFX> tag appears before <OFX>");
+    }
+
+    #[rstest]
+    #[case("Content-Type: application/x-ofx\n\nOFXHEADER:100", "OFXHEADER:100")]
+    #[case("junk\n<?xml version=\"1.0\"?><OFX>", "<?xml version=\"1.0\"?><OFX>")]
+    #[case("junk<OFX></OFX>", "<OFX></OFX>")]
+    #[case("no markers", "no markers")]
+    fn test_skip_preamble(#[case] content: &str, #[case] expected: &str) {
+        assert_eq!(skip_preamble(content), expected);
+    }
+
     #[test]
     fn test_parse_xml_bank_statement() {
         let result = QfxParser::parse(SAMPLE_XML_QFX);
@@ -235,120 +721,174 @@ VERSION:102
         assert_eq!(txn.fitid, Some("202512250".to_string()));
         assert_eq!(txn.name, Some("ACME Corp".to_string()));
         assert_eq!(txn.memo, None);
+        assert_eq!(txn.account_id, None);
     }
 
     #[test]
-    fn test_parse_sgml_statement() {
-        let result = QfxParser::parse(SAMPLE_SGML_QFX);
-        assert!(result.is_ok());
+    fn test_parse_check_and_reference_numbers() {
+        let content = SAMPLE_XML_QFX.replace(
+            "<TRNTYPE>DEBIT</TRNTYPE>",
+            "<TRNTYPE>CHECK</TRNTYPE><CHECKNUM>1234</CHECKNUM><REFNUM>REF-77</REFNUM>",
+        );
+        let transactions = QfxParser::parse(&content).unwrap();
+        assert_eq!(transactions[0].check_num.as_deref(), Some("1234"));
+        assert_eq!(transactions[0].ref_num.as_deref(), Some("REF-77"));
 
-        let transactions = result.unwrap();
-        assert_eq!(transactions.len(), 1);
+        let transactions = QfxParser::parse(SAMPLE_XML_QFX).unwrap();
+        assert_eq!(transactions[0].check_num, None);
+        assert_eq!(transactions[0].ref_num, None);
+    }
 
-        let txn = &transactions[0];
-        assert_eq!(txn.trn_type, "DEBIT");
-        assert_eq!(txn.amount.to_string(), "-50.00");
-        assert_eq!(txn.fitid, Some("202512260".to_string()));
-        assert_eq!(txn.name, Some("Coffee Shop".to_string()));
+    #[test]
+    fn test_parse_sgml_check_number() {
+        let content = SAMPLE_SGML_QFX.replace(
+            "<TRNTYPE>DEBIT\n",
+            "<TRNTYPE>CHECK\n<CHECKNUM>1234\n<REFNUM>REF-77\n",
+        );
+        let transactions = QfxParser::parse(&content).unwrap();
+        assert_eq!(transactions[0].check_num.as_deref(), Some("1234"));
+        assert_eq!(transactions[0].ref_num.as_deref(), Some("REF-77"));
+        assert_eq!(transactions[0].name.as_deref(), Some("Coffee Shop"));
     }
 
     #[test]
-    fn test_parse_missing_ofx_tag() {
-        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
-<INVALID>
-</INVALID>"#;
+    fn test_parse_dt_user() {
+        let transactions = QfxParser::parse(SAMPLE_SGML_QFX).unwrap();
+        assert!(transactions[0].dt_user.is_none());
 
-        let result = QfxParser::parse(content);
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("Missing <OFX> tag"));
+        let content = SAMPLE_XML_QFX.replace(
+            "<DTPOSTED>20251226120000</DTPOSTED>",
+            "<DTPOSTED>20251226120000</DTPOSTED><DTUSER>20251224</DTUSER>",
+        );
+        let transactions = QfxParser::parse(&content).unwrap();
+        let dt_user = transactions[0].dt_user.clone().unwrap();
+        assert_eq!(
+            NaiveDate::try_from(dt_user).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 12, 24).unwrap()
+        );
     }
 
     #[test]
-    fn test_parse_missing_closing_ofx_tag() {
-        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
-<OFX>
-<BANKMSGSRSV1>
-</BANKMSGSRSV1>"#;
-
-        let result = QfxParser::parse(content);
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("Missing </OFX> tag"));
+    fn test_parse_sgml_dt_user() {
+        let content = SAMPLE_SGML_QFX.replace(
+            "<DTPOSTED>20251226120000\n",
+            "<DTPOSTED>20251226120000\n<DTUSER>20251223\n",
+        );
+        let transactions = QfxParser::parse(&content).unwrap();
+        let dt_user = transactions[0].dt_user.clone().unwrap();
+        assert_eq!(
+            NaiveDate::try_from(dt_user).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 12, 23).unwrap()
+        );
     }
 
     #[test]
-    fn test_parse_no_transaction_data() {
+    fn test_parse_credit_card_account_id() {
         let content = r#"<?xml version="1.0" encoding="UTF-8"?>
 <OFX>
+    <CREDITCARDMSGSRSV1>
+        <CCSTMTTRNRS>
+            <CCSTMTRS>
+                <CURDEF>USD</CURDEF>
+                <CCACCTFROM>
+                    <ACCTID>XXXXXXXXXXXX1234</ACCTID>
+                </CCACCTFROM>
+                <BANKTRANLIST>
+                    <STMTTRN>
+                        <TRNTYPE>DEBIT</TRNTYPE>
+                        <DTPOSTED>20251225120000</DTPOSTED>
+                        <TRNAMT>-20.00</TRNAMT>
+                    </STMTTRN>
+                    <STMTTRN>
+                        <TRNTYPE>CREDIT</TRNTYPE>
+                        <DTPOSTED>20251226120000</DTPOSTED>
+                        <TRNAMT>20.00</TRNAMT>
+                    </STMTTRN>
+                </BANKTRANLIST>
+            </CCSTMTRS>
+        </CCSTMTTRNRS>
+    </CREDITCARDMSGSRSV1>
 </OFX>"#;
 
-        let result = QfxParser::parse(content);
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("No transaction data found"));
+        let transactions = QfxParser::parse(content).unwrap();
+        assert_eq!(transactions.len(), 2);
+        for txn in &transactions {
+            assert_eq!(txn.account_id, Some("XXXXXXXXXXXX1234".to_string()));
+            assert_eq!(txn.currency, Some("USD".to_string()));
+        }
     }
 
     #[test]
-    fn test_parse_invalid_xml() {
-        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+    fn test_parse_sgml_credit_card_account_id() {
+        let content = r#"OFXHEADER:100
+DATA:OFXSGML
+
 <OFX>
-<BANKMSGSRSV1>
-<INVALID XML
+<CREDITCARDMSGSRSV1>
+<CCSTMTTRNRS>
+<CCSTMTRS>
+<CCACCTFROM>
+<ACCTID>XXXXXXXXXXXX5678
+</CCACCTFROM>
+<BANKTRANLIST>
+<STMTTRN>
+<TRNTYPE>DEBIT
+<DTPOSTED>20251226
+<TRNAMT>-12.50
+</STMTTRN>
+</BANKTRANLIST>
+</CCSTMTRS>
+</CCSTMTTRNRS>
+</CREDITCARDMSGSRSV1>
 </OFX>"#;
 
-        let result = QfxParser::parse(content);
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("XML parse error"));
+        let transactions = QfxParser::parse(content).unwrap();
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(
+            transactions[0].account_id,
+            Some("XXXXXXXXXXXX5678".to_string())
+        );
     }
 
     #[test]
-    fn test_parse_invalid_amount_in_transaction() {
+    fn test_parse_multiple_bank_accounts() {
         let content = r#"<?xml version="1.0" encoding="UTF-8"?>
 <OFX>
     <BANKMSGSRSV1>
         <STMTTRNRS>
             <STMTRS>
+                <CURDEF>USD</CURDEF>
+                <BANKACCTFROM>
+                    <ACCTID>111</ACCTID>
+                </BANKACCTFROM>
                 <BANKTRANLIST>
                     <STMTTRN>
                         <TRNTYPE>DEBIT</TRNTYPE>
-                        <DTPOSTED>20251226120000</DTPOSTED>
-                        <TRNAMT>invalid_amount</TRNAMT>
-                        <FITID>202512260</FITID>
+                        <DTPOSTED>20251225120000</DTPOSTED>
+                        <TRNAMT>-20.00</TRNAMT>
+                        <FITID>A1</FITID>
                     </STMTTRN>
                 </BANKTRANLIST>
             </STMTRS>
         </STMTTRNRS>
-    </BANKMSGSRSV1>
-</OFX>"#;
-
-        let result = QfxParser::parse(content);
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("Invalid amount"));
-    }
-
-    #[test]
-    fn test_parse_multiple_transactions() {
-        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
-<OFX>
-    <BANKMSGSRSV1>
         <STMTTRNRS>
             <STMTRS>
+                <CURDEF>EUR</CURDEF>
+                <BANKACCTFROM>
+                    <ACCTID>222</ACCTID>
+                </BANKACCTFROM>
                 <BANKTRANLIST>
-                    <STMTTRN>
-                        <TRNTYPE>DEBIT</TRNTYPE>
-                        <DTPOSTED>20251226120000</DTPOSTED>
-                        <TRNAMT>-50.00</TRNAMT>
-                        <FITID>1</FITID>
-                    </STMTTRN>
                     <STMTTRN>
                         <TRNTYPE>CREDIT</TRNTYPE>
-                        <DTPOSTED>20251227120000</DTPOSTED>
-                        <TRNAMT>1500.00</TRNAMT>
-                        <FITID>2</FITID>
+                        <DTPOSTED>20251226120000</DTPOSTED>
+                        <TRNAMT>35.00</TRNAMT>
+                        <FITID>B1</FITID>
                     </STMTTRN>
                     <STMTTRN>
                         <TRNTYPE>DEBIT</TRNTYPE>
-                        <DTPOSTED>20251228120000</DTPOSTED>
-                        <TRNAMT>-25.00</TRNAMT>
-                        <FITID>3</FITID>
+                        <DTPOSTED>20251227120000</DTPOSTED>
+                        <TRNAMT>-5.00</TRNAMT>
+                        <FITID>B2</FITID>
                     </STMTTRN>
                 </BANKTRANLIST>
             </STMTRS>
@@ -356,16 +896,1176 @@ VERSION:102
     </BANKMSGSRSV1>
 </OFX>"#;
 
-        let result = QfxParser::parse(content);
+        let transactions = QfxParser::parse(content).unwrap();
+        let accounts: Vec<_> = transactions
+            .iter()
+            .map(|txn| (txn.fitid.as_deref(), txn.account_id.as_deref()))
+            .collect();
+        assert_eq!(
+            accounts,
+            [
+                (Some("A1"), Some("111")),
+                (Some("B1"), Some("222")),
+                (Some("B2"), Some("222")),
+            ]
+        );
+        assert_eq!(transactions[0].currency, Some("USD".to_string()));
+        assert_eq!(transactions[2].currency, Some("EUR".to_string()));
+    }
+
+    #[test]
+    fn test_parse_sgml_statement() {
+        let result = QfxParser::parse(SAMPLE_SGML_QFX);
         assert!(result.is_ok());
 
         let transactions = result.unwrap();
-        assert_eq!(transactions.len(), 3);
+        assert_eq!(transactions.len(), 1);
+
+        let txn = &transactions[0];
+        assert_eq!(txn.trn_type, "DEBIT");
+        assert_eq!(txn.amount.to_string(), "-50.00");
+        assert_eq!(txn.fitid, Some("202512260".to_string()));
+        assert_eq!(txn.name, Some("Coffee Shop".to_string()));
+        assert_eq!(txn.currency, Some("USD".to_string()));
+    }
+
+    fn sample_with_status(code: &str, severity: &str, message: &str) -> String {
+        SAMPLE_SGML_QFX.replace(
+            "<TRNUID>1\n",
+            &format!(
+                "<TRNUID>1\n<STATUS>\n<CODE>{}\n<SEVERITY>{}\n<MESSAGE>{}\n</STATUS>\n",
+                code, severity, message
+            ),
+        )
+    }
+
+    fn parse_collecting_warnings(
+        content: &str,
+    ) -> (Result<Vec<QfxTransaction>, String>, Vec<ParseWarning>) {
+        let mut warnings = Vec::new();
+        let result = QfxParser::parse_with_warnings(content, &QfxOptions::default(), &mut |w| {
+            warnings.push(w.clone())
+        });
+        (result, warnings)
+    }
+
+    const SAMPLE_TRUNCATED_QFX: &str = r#"OFXHEADER:100
+DATA:OFXSGML
+VERSION:102
+
+<OFX>
+<BANKMSGSRSV1>
+<STMTTRNRS>
+<STMTRS>
+<CURDEF>USD
+<BANKTRANLIST>
+<DTSTART>20251201
+<DTEND>20251231
+<TRNCOUNT>3
+<STMTTRN>
+<TRNTYPE>DEBIT
+<DTPOSTED>20251226120000
+<TRNAMT>-50.00
+<FITID>202512260
+<NAME>Coffee Shop
+</STMTTRN>
+<STMTTRN>
+<TRNTYPE>CREDIT
+<DTPOSTED>20251227120000
+<TRNAMT>1500.00
+<FITID>202512270
+<NAME>Payroll
+</STMTTRN>
+</BANKTRANLIST>
+</STMTRS>
+</STMTTRNRS>
+</BANKMSGSRSV1>
+</OFX>
+"#;
+
+    #[test]
+    fn test_parse_trncount_mismatch_emits_warning() {
+        let (result, warnings) = parse_collecting_warnings(SAMPLE_TRUNCATED_QFX);
+        assert_eq!(result.unwrap().len(), 2);
+        assert_eq!(
+            warnings,
+            vec![ParseWarning::TransactionCountMismatch {
+                declared: 3,
+                actual: 2,
+            }]
+        );
+    }
+
+    #[rstest]
+    #[case("<TRNCOUNT>2")]
+    #[case("<TRNCOUNT>n/a")]
+    fn test_parse_trncount_without_mismatch(#[case] trncount: &str) {
+        let content = SAMPLE_TRUNCATED_QFX.replace("<TRNCOUNT>3", trncount);
+        let (result, warnings) = parse_collecting_warnings(&content);
+        assert_eq!(result.unwrap().len(), 2);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_parse_status_info_success() {
+        let (result, warnings) = parse_collecting_warnings(&sample_with_status("0", "INFO", "OK"));
+        assert_eq!(result.unwrap().len(), 1);
+        assert!(warnings.is_empty());
+    }
+
+    #[rstest]
+    #[case("2000", "WARN", "Statement may be incomplete")]
+    #[case("3000", "INFO", "Closing balance unavailable")]
+    fn test_parse_status_advisory_emits_warning(
+        #[case] code: &str,
+        #[case] severity: &str,
+        #[case] message: &str,
+    ) {
+        let (result, warnings) =
+            parse_collecting_warnings(&sample_with_status(code, severity, message));
+        assert_eq!(result.unwrap().len(), 1);
+        assert_eq!(
+            warnings,
+            vec![ParseWarning::OfxStatus {
+                code: code.to_string(),
+                message: Some(message.to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_status_error_fails() {
+        let (result, warnings) =
+            parse_collecting_warnings(&sample_with_status("2000", "ERROR", "General error"));
+        assert_eq!(result.unwrap_err(), "OFX status error 2000: General error");
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_parse_status_error_without_statement() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<OFX>
+    <CREDITCARDMSGSRSV1>
+        <CCSTMTTRNRS>
+            <TRNUID>1</TRNUID>
+            <STATUS>
+                <CODE>2003</CODE>
+                <SEVERITY>ERROR</SEVERITY>
+            </STATUS>
+        </CCSTMTTRNRS>
+    </CREDITCARDMSGSRSV1>
+</OFX>"#;
+
+        let result = QfxParser::parse(content);
+        assert_eq!(result.unwrap_err(), "OFX status error 2003: no message");
+    }
+
+    #[test]
+    fn test_parse_missing_ofx_tag() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<INVALID>
+</INVALID>"#;
+
+        let result = QfxParser::parse(content);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("Missing <OFX> tag"));
+    }
+
+    #[test]
+    fn test_parse_missing_closing_ofx_tag() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<OFX>
+<BANKMSGSRSV1>
+</BANKMSGSRSV1>"#;
+
+        let result = QfxParser::parse(content);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("Missing </OFX> tag"));
+    }
+
+    #[test]
+    fn test_parse_no_transaction_data() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<OFX>
+</OFX>"#;
+
+        let result = QfxParser::parse(content);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("No transaction data found"));
+    }
+
+    #[test]
+    fn test_parse_invalid_xml() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<OFX>
+<BANKMSGSRSV1>
+<INVALID XML
+</OFX>"#;
+
+        let result = QfxParser::parse(content);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("XML parse error"));
+    }
+
+    #[test]
+    fn test_parse_invalid_amount_in_transaction() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<OFX>
+    <BANKMSGSRSV1>
+        <STMTTRNRS>
+            <STMTRS>
+                <BANKTRANLIST>
+                    <STMTTRN>
+                        <TRNTYPE>DEBIT</TRNTYPE>
+                        <DTPOSTED>20251226120000</DTPOSTED>
+                        <TRNAMT>invalid_amount</TRNAMT>
+                        <FITID>202512260</FITID>
+                    </STMTTRN>
+                </BANKTRANLIST>
+            </STMTRS>
+        </STMTTRNRS>
+    </BANKMSGSRSV1>
+</OFX>"#;
+
+        let result = QfxParser::parse(content);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("Invalid amount"));
+    }
+
+    #[test]
+    fn test_parse_invalid_amount_reports_stmttrn_index() {
+        let content = SAMPLE_NO_STMTRS_QFX.replace("<TRNAMT>1500.00", "<TRNAMT>15x0");
+
+        let error = QfxParser::parse(&content).unwrap_err();
+        assert!(error.starts_with("STMTTRN 2: "), "{error}");
+    }
+
+    #[test]
+    fn test_parse_multiple_bank_transaction_lists() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<OFX>
+    <BANKMSGSRSV1>
+        <STMTTRNRS>
+            <STMTRS>
+                <BANKTRANLIST>
+                    <DTSTART>20251201</DTSTART>
+                    <DTEND>20251215</DTEND>
+                    <STMTTRN>
+                        <TRNTYPE>DEBIT</TRNTYPE>
+                        <DTPOSTED>20251205120000</DTPOSTED>
+                        <TRNAMT>-10.00</TRNAMT>
+                        <FITID>1</FITID>
+                    </STMTTRN>
+                    <STMTTRN>
+                        <TRNTYPE>DEBIT</TRNTYPE>
+                        <DTPOSTED>20251210120000</DTPOSTED>
+                        <TRNAMT>-20.00</TRNAMT>
+                        <FITID>2</FITID>
+                    </STMTTRN>
+                </BANKTRANLIST>
+                <BANKTRANLIST>
+                    <DTSTART>20251216</DTSTART>
+                    <DTEND>20251231</DTEND>
+                    <STMTTRN>
+                        <TRNTYPE>CREDIT</TRNTYPE>
+                        <DTPOSTED>20251220120000</DTPOSTED>
+                        <TRNAMT>30.00</TRNAMT>
+                        <FITID>3</FITID>
+                    </STMTTRN>
+                </BANKTRANLIST>
+            </STMTRS>
+        </STMTTRNRS>
+    </BANKMSGSRSV1>
+</OFX>"#;
+
+        let transactions = QfxParser::parse(content).unwrap();
+        let fitids: Vec<_> = transactions
+            .iter()
+            .map(|t| t.fitid.as_deref().unwrap())
+            .collect();
+        assert_eq!(fitids, vec!["1", "2", "3"]);
+    }
+
+    #[test]
+    fn test_parse_multiple_cc_bank_transaction_lists_sgml() {
+        let content = r#"OFXHEADER:100
+DATA:OFXSGML
+
+<OFX>
+<CREDITCARDMSGSRSV1>
+<CCSTMTTRNRS>
+<CCSTMTRS>
+<BANKTRANLIST>
+<STMTTRN>
+<TRNTYPE>DEBIT
+<DTPOSTED>20251205
+<TRNAMT>-10.00
+<FITID>1
+</STMTTRN>
+</BANKTRANLIST>
+<BANKTRANLIST>
+<STMTTRN>
+<TRNTYPE>DEBIT
+<DTPOSTED>20251220
+<TRNAMT>-20.00
+<FITID>2
+</STMTTRN>
+</BANKTRANLIST>
+</CCSTMTRS>
+</CCSTMTTRNRS>
+</CREDITCARDMSGSRSV1>
+</OFX>"#;
+
+        let transactions = QfxParser::parse(content).unwrap();
+        assert_eq!(transactions.len(), 2);
+        assert_eq!(transactions[0].fitid, Some("1".to_string()));
+        assert_eq!(transactions[1].fitid, Some("2".to_string()));
+    }
+
+    const SAMPLE_MONEY_QFX: &str = r#"OFXHEADER:100
+DATA:OFXSGML
+VERSION:102
+
+<OFX>
+<SIGNONMSGSRSV1>
+<SONRS>
+<STATUS>
+<CODE>0
+<SEVERITY>INFO
+</STATUS>
+<DTSERVER>20251231120000
+<LANGUAGE>ENG
+</SONRS>
+<STMTTRNRS>
+<TRNUID>1
+<STMTRS>
+<CURDEF>USD
+<BANKTRANLIST>
+<STMTTRN>
+<TRNTYPE>DEBIT
+<DTPOSTED>20251226120000
+<TRNAMT>-50.00
+<FITID>202512260
+<NAME>Coffee Shop
+</STMTTRN>
+</BANKTRANLIST>
+</STMTRS>
+</STMTTRNRS>
+</SIGNONMSGSRSV1>
+</OFX>"#;
+
+    #[test]
+    fn test_parse_transaction_currency_overrides_curdef() {
+        let content = SAMPLE_SGML_QFX.replace(
+            "<TRNAMT>-50.00\n",
+            "<TRNAMT>-50.00\n<CURRENCY>\n<CURRATE>1.1\n<CURSYM>EUR\n</CURRENCY>\n",
+        );
+
+        let transactions = QfxParser::parse(&content).unwrap();
+        assert_eq!(transactions[0].currency, Some("EUR".to_string()));
+
+        let transactions = QfxParser::parse(SAMPLE_SGML_QFX).unwrap();
+        assert_eq!(transactions[0].currency, Some("USD".to_string()));
+    }
+
+    #[test]
+    fn test_parse_sgml_vendor_tags_and_field_order() {
+        let content = SAMPLE_SGML_QFX.replace(
+            "<DTPOSTED>20251226120000\n<TRNAMT>-50.00\n<FITID>202512260\n",
+            "<TRNAMT>-50.00\n<PRIME.XYZ>abc 123\n<DTPOSTED>20251226120000\n<INTU.PTYPE>1\n<FITID>202512260\n",
+        );
+
+        let transactions = QfxParser::parse(&content).unwrap();
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].amount.to_string(), "-50.00");
+        assert_eq!(transactions[0].dt_posted.as_str(), "20251226120000");
+        assert_eq!(transactions[0].fitid, Some("202512260".to_string()));
+        assert_eq!(transactions[0].name, Some("Coffee Shop".to_string()));
+    }
+
+    #[test]
+    fn test_parse_xml_vendor_tags() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<OFX>
+    <BANKMSGSRSV1>
+        <STMTTRNRS>
+            <STMTRS>
+                <BANKTRANLIST>
+                    <STMTTRN>
+                        <TRNTYPE>DEBIT</TRNTYPE>
+                        <TRNAMT>-50.00</TRNAMT>
+                        <PRIME.XYZ>abc</PRIME.XYZ>
+                        <FITID>202512260</FITID>
+                        <VENDOR.EXT><CODE>7</CODE></VENDOR.EXT>
+                        <DTPOSTED>20251226120000</DTPOSTED>
+                    </STMTTRN>
+                </BANKTRANLIST>
+            </STMTRS>
+        </STMTTRNRS>
+    </BANKMSGSRSV1>
+</OFX>"#;
+
+        let transactions = QfxParser::parse(content).unwrap();
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].amount.to_string(), "-50.00");
+        assert_eq!(transactions[0].fitid, Some("202512260".to_string()));
+    }
+
+    const SAMPLE_FOREIGN_QFX: &str = r#"OFXHEADER:100
+DATA:OFXSGML
+
+<OFX>
+<CREDITCARDMSGSRSV1>
+<CCSTMTTRNRS>
+<CCSTMTRS>
+<CURDEF>USD
+<BANKTRANLIST>
+<STMTTRN>
+<TRNTYPE>DEBIT
+<DTPOSTED>20251226
+<TRNAMT>-50.00
+<FITID>1
+<NAME>Hotel Paris
+<CURRENCY>
+<CURRATE>1.1
+<CURSYM>EUR
+</CURRENCY>
+</STMTTRN>
+<STMTTRN>
+<TRNTYPE>DEBIT
+<DTPOSTED>20251227
+<TRNAMT>-20.00
+<FITID>2
+<NAME>Grocery
+</STMTTRN>
+</BANKTRANLIST>
+</CCSTMTRS>
+</CCSTMTTRNRS>
+</CREDITCARDMSGSRSV1>
+</OFX>"#;
+
+    #[test]
+    fn test_parse_apply_ofx_rates() {
+        let options = QfxOptions {
+            apply_ofx_rates: true,
+            ..QfxOptions::default()
+        };
+
+        let transactions = QfxParser::parse_with_options(SAMPLE_FOREIGN_QFX, &options).unwrap();
+        let foreign = &transactions[0];
+        assert_eq!(foreign.amount.to_string(), "-55.00");
+        assert_eq!(foreign.currency, Some("USD".to_string()));
+        assert_eq!(foreign.original_amount.unwrap().to_string(), "-50.00");
+        assert_eq!(foreign.original_currency, Some("EUR".to_string()));
+
+        let domestic = &transactions[1];
+        assert_eq!(domestic.amount.to_string(), "-20.00");
+        assert_eq!(domestic.currency, Some("USD".to_string()));
+        assert_eq!(domestic.original_amount, None);
+    }
+
+    #[test]
+    fn test_parse_apply_ofx_rates_high_precision() {
+        let options = QfxOptions {
+            apply_ofx_rates: true,
+            ..QfxOptions::default()
+        };
+        let content = SAMPLE_FOREIGN_QFX.replace("<CURRATE>1.1", "<CURRATE>1.123456789012345");
+
+        let transactions = QfxParser::parse_with_options(&content, &options).unwrap();
+        assert_eq!(transactions[0].amount.to_string(), "-56.17");
+        assert_eq!(
+            transactions[0].original_amount.unwrap().to_string(),
+            "-50.00"
+        );
+    }
+
+    #[test]
+    fn test_parse_apply_ofx_rates_overflow() {
+        let options = QfxOptions {
+            apply_ofx_rates: true,
+            collect_row_errors: true,
+            ..QfxOptions::default()
+        };
+        let content = SAMPLE_FOREIGN_QFX
+            .replace("<TRNAMT>-50.00", "<TRNAMT>-79228162514264337593543950335")
+            .replace("<CURRATE>1.1", "<CURRATE>2");
+
+        let statement =
+            QfxParser::parse_statement_with_warnings(&content, &options, &mut |_| {}).unwrap();
+        assert_eq!(statement.transactions.len(), 1);
+        assert_eq!(
+            statement.row_errors,
+            vec![RowError {
+                row: 1,
+                message: "Currency rate overflows the amount".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_ofx_rates_not_applied_by_default() {
+        let transactions = QfxParser::parse(SAMPLE_FOREIGN_QFX).unwrap();
+        assert_eq!(transactions[0].amount.to_string(), "-50.00");
+        assert_eq!(transactions[0].currency, Some("EUR".to_string()));
+        assert_eq!(transactions[0].original_amount, None);
+    }
+
+    #[test]
+    fn test_parse_original_currency() {
+        let content = SAMPLE_SGML_QFX.replace(
+            "<TRNAMT>-50.00\n",
+            "<TRNAMT>-55.00\n<ORIGCURRENCY>\n<CURRATE>1.1\n<CURSYM>EUR\n</ORIGCURRENCY>\n",
+        );
+
+        let transactions = QfxParser::parse(&content).unwrap();
+        let txn = &transactions[0];
+        assert_eq!(txn.amount.to_string(), "-55.00");
+        assert_eq!(txn.currency, Some("USD".to_string()));
+        assert_eq!(txn.original_currency, Some("EUR".to_string()));
+        assert_eq!(txn.original_amount.unwrap().to_string(), "-50.00");
+    }
+
+    #[test]
+    fn test_parse_original_currency_without_rate() {
+        let content = SAMPLE_SGML_QFX.replace(
+            "<TRNAMT>-50.00\n",
+            "<TRNAMT>-50.00\n<ORIGCURRENCY>\n<CURSYM>GBP\n</ORIGCURRENCY>\n",
+        );
+
+        let transactions = QfxParser::parse(&content).unwrap();
+        assert_eq!(transactions[0].original_currency, Some("GBP".to_string()));
+        assert_eq!(transactions[0].original_amount, None);
+    }
+
+    #[test]
+    fn test_parse_original_currency_rate_overflow() {
+        let content = SAMPLE_SGML_QFX.replace(
+            "<TRNAMT>-50.00\n",
+            "<TRNAMT>-79228162514264337593543950335\n<ORIGCURRENCY>\n<CURRATE>0.5\n<CURSYM>EUR\n</ORIGCURRENCY>\n",
+        );
+
+        let result = QfxParser::parse(&content);
+        assert_eq!(
+            result.unwrap_err(),
+            "STMTTRN 1: Currency rate overflows the amount"
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_original_currency_rate() {
+        let content = SAMPLE_SGML_QFX.replace(
+            "<TRNAMT>-50.00\n",
+            "<TRNAMT>-50.00\n<ORIGCURRENCY>\n<CURRATE>abc\n<CURSYM>EUR\n</ORIGCURRENCY>\n",
+        );
+
+        let result = QfxParser::parse(&content);
+        assert!(result.unwrap_err().contains("Invalid currency rate"));
+    }
+
+    #[test]
+    fn test_parse_self_closing_tags_are_none() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<OFX>
+    <BANKMSGSRSV1>
+        <STMTTRNRS>
+            <STMTRS>
+                <BANKTRANLIST>
+                    <STMTTRN>
+                        <TRNTYPE>DEBIT</TRNTYPE>
+                        <DTPOSTED>20251226120000</DTPOSTED>
+                        <DTUSER/>
+                        <TRNAMT>-50.00</TRNAMT>
+                        <FITID/>
+                        <NAME>Coffee Shop</NAME>
+                        <MEMO/>
+                    </STMTTRN>
+                    <STMTTRN>
+                        <TRNTYPE>CREDIT</TRNTYPE>
+                        <DTPOSTED>20251227120000</DTPOSTED>
+                        <TRNAMT>10.00</TRNAMT>
+                        <NAME></NAME>
+                        <MEMO>Refund</MEMO>
+                    </STMTTRN>
+                </BANKTRANLIST>
+            </STMTRS>
+        </STMTTRNRS>
+    </BANKMSGSRSV1>
+</OFX>"#;
+
+        let transactions = QfxParser::parse(content).unwrap();
+        assert_eq!(transactions.len(), 2);
+        assert!(transactions[0].dt_user.is_none());
+        assert_eq!(transactions[0].fitid, None);
+        assert_eq!(transactions[0].memo, None);
+        assert_eq!(transactions[0].name, Some("Coffee Shop".to_string()));
+        assert_eq!(transactions[1].name, None);
+        assert_eq!(transactions[1].memo, Some("Refund".to_string()));
+    }
+
+    #[test]
+    fn test_parse_money_statement_under_signon() {
+        let transactions = QfxParser::parse(SAMPLE_MONEY_QFX).unwrap();
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].fitid, Some("202512260".to_string()));
+        assert_eq!(transactions[0].name, Some("Coffee Shop".to_string()));
+    }
+
+    #[test]
+    fn test_parse_money_cc_statement_under_signon() {
+        let content = SAMPLE_MONEY_QFX
+            .replace("STMTTRNRS>", "CCSTMTTRNRS>")
+            .replace("STMTRS>", "CCSTMTRS>");
+
+        let transactions = QfxParser::parse(&content).unwrap();
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].amount.to_string(), "-50.00");
+    }
+
+    #[test]
+    fn test_parse_money_bank_and_cc_statements_under_signon() {
+        let content = SAMPLE_MONEY_QFX.replace(
+            "</STMTTRNRS>\n",
+            "</STMTTRNRS>\n<CCSTMTTRNRS>\n<TRNUID>2\n<CCSTMTRS>\n<CURDEF>USD\n<BANKTRANLIST>\n\
+             <STMTTRN>\n<TRNTYPE>DEBIT\n<DTPOSTED>20251227120000\n<TRNAMT>-20.00\n\
+             <FITID>CC1\n<NAME>Grocery\n</STMTTRN>\n</BANKTRANLIST>\n</CCSTMTRS>\n\
+             </CCSTMTTRNRS>\n",
+        );
+
+        let transactions = QfxParser::parse(&content).unwrap();
+        assert_eq!(transactions.len(), 2);
+        assert_eq!(transactions[0].fitid, Some("202512260".to_string()));
+        assert_eq!(transactions[1].fitid, Some("CC1".to_string()));
+        assert_eq!(transactions[1].amount.to_string(), "-20.00");
+    }
+
+    const SAMPLE_NO_STMTRS_QFX: &str = r#"OFXHEADER:100
+DATA:OFXSGML
+VERSION:102
+
+<OFX>
+<BANKMSGSRSV1>
+<STMTTRNRS>
+<TRNUID>1
+<STATUS>
+<CODE>0
+<SEVERITY>INFO
+</STATUS>
+<BANKTRANLIST>
+<DTSTART>20251201
+<DTEND>20251231
+<STMTTRN>
+<TRNTYPE>DEBIT
+<DTPOSTED>20251226120000
+<TRNAMT>-50.00
+<FITID>202512260
+<NAME>Coffee Shop
+</STMTTRN>
+<STMTTRN>
+<TRNTYPE>CREDIT
+<DTPOSTED>20251227120000
+<TRNAMT>1500.00
+<FITID>202512270
+<NAME>Payroll
+</STMTTRN>
+</BANKTRANLIST>
+</STMTTRNRS>
+</BANKMSGSRSV1>
+</OFX>
+"#;
+
+    #[test]
+    fn test_parse_banktranlist_without_stmtrs() {
+        let transactions = QfxParser::parse(SAMPLE_NO_STMTRS_QFX).unwrap();
+        assert_eq!(transactions.len(), 2);
+        assert_eq!(transactions[0].fitid, Some("202512260".to_string()));
+        assert_eq!(transactions[1].name, Some("Payroll".to_string()));
+    }
+
+    #[test]
+    fn test_parse_signon_only_has_no_transaction_data() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<OFX>
+    <SIGNONMSGSRSV1>
+        <SONRS>
+            <LANGUAGE>ENG</LANGUAGE>
+        </SONRS>
+    </SIGNONMSGSRSV1>
+</OFX>"#;
+
+        let result = QfxParser::parse(content);
+        assert!(result.unwrap_err().contains("No transaction data found"));
+    }
+
+    #[test]
+    fn test_parse_nested_amount_element() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<OFX>
+    <BANKMSGSRSV1>
+        <STMTTRNRS>
+            <STMTRS>
+                <BANKTRANLIST>
+                    <STMTTRN>
+                        <TRNTYPE>DEBIT</TRNTYPE>
+                        <DTPOSTED>20251226120000</DTPOSTED>
+                        <TRNAMT><AMOUNT>-50.00</AMOUNT><CURSYM>USD</CURSYM></TRNAMT>
+                        <FITID>202512260</FITID>
+                    </STMTTRN>
+                    <STMTTRN>
+                        <TRNTYPE>CREDIT</TRNTYPE>
+                        <DTPOSTED>20251227120000</DTPOSTED>
+                        <TRNAMT>1500.00</TRNAMT>
+                        <FITID>202512270</FITID>
+                    </STMTTRN>
+                </BANKTRANLIST>
+            </STMTRS>
+        </STMTTRNRS>
+    </BANKMSGSRSV1>
+</OFX>"#;
+
+        let result = QfxParser::parse(content);
+        assert!(result.is_ok());
+
+        let transactions = result.unwrap();
+        assert_eq!(transactions.len(), 2);
+        assert_eq!(transactions[0].amount.to_string(), "-50.00");
+        assert_eq!(transactions[1].amount.to_string(), "1500.00");
+    }
+
+    #[test]
+    fn test_parse_nested_amount_missing_amount_child() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<OFX>
+    <BANKMSGSRSV1>
+        <STMTTRNRS>
+            <STMTRS>
+                <BANKTRANLIST>
+                    <STMTTRN>
+                        <TRNTYPE>DEBIT</TRNTYPE>
+                        <DTPOSTED>20251226120000</DTPOSTED>
+                        <TRNAMT><CURSYM>USD</CURSYM></TRNAMT>
+                    </STMTTRN>
+                </BANKTRANLIST>
+            </STMTRS>
+        </STMTTRNRS>
+    </BANKMSGSRSV1>
+</OFX>"#;
+
+        let result = QfxParser::parse(content);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("Invalid amount"));
+    }
+
+    #[rstest]
+    #[case("<TRNAMT>\n<AMOUNT>-50.00\n<CURSYM>USD\n</TRNAMT>")]
+    #[case("<TRNAMT><AMOUNT>-50.00\n<CURSYM>USD\n</TRNAMT>")]
+    fn test_parse_sgml_nested_amount_element(#[case] amount: &str) {
+        let content = SAMPLE_SGML_QFX.replace("<TRNAMT>-50.00", amount);
+
+        let transactions = QfxParser::parse(&content).unwrap();
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].amount.to_string(), "-50.00");
+        assert_eq!(transactions[0].fitid, Some("202512260".to_string()));
+        assert_eq!(transactions[0].name, Some("Coffee Shop".to_string()));
+    }
+
+    #[test]
+    fn test_parse_multiple_transactions() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<OFX>
+    <BANKMSGSRSV1>
+        <STMTTRNRS>
+            <STMTRS>
+                <BANKTRANLIST>
+                    <STMTTRN>
+                        <TRNTYPE>DEBIT</TRNTYPE>
+                        <DTPOSTED>20251226120000</DTPOSTED>
+                        <TRNAMT>-50.00</TRNAMT>
+                        <FITID>1</FITID>
+                    </STMTTRN>
+                    <STMTTRN>
+                        <TRNTYPE>CREDIT</TRNTYPE>
+                        <DTPOSTED>20251227120000</DTPOSTED>
+                        <TRNAMT>1500.00</TRNAMT>
+                        <FITID>2</FITID>
+                    </STMTTRN>
+                    <STMTTRN>
+                        <TRNTYPE>DEBIT</TRNTYPE>
+                        <DTPOSTED>20251228120000</DTPOSTED>
+                        <TRNAMT>-25.00</TRNAMT>
+                        <FITID>3</FITID>
+                    </STMTTRN>
+                </BANKTRANLIST>
+            </STMTRS>
+        </STMTTRNRS>
+    </BANKMSGSRSV1>
+</OFX>"#;
+
+        let result = QfxParser::parse(content);
+        assert!(result.is_ok());
+
+        let transactions = result.unwrap();
+        assert_eq!(transactions.len(), 3);
         assert_eq!(transactions[0].trn_type, "DEBIT");
         assert_eq!(transactions[1].trn_type, "CREDIT");
         assert_eq!(transactions[2].trn_type, "DEBIT");
     }
 
+    const SAMPLE_OFX_CLOSE_IN_MEMO: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<OFX>
+    <BANKMSGSRSV1>
+        <STMTTRNRS>
+            <STMTRS>
+                <BANKTRANLIST>
+                    <STMTTRN>
+                        <TRNTYPE>DEBIT</TRNTYPE>
+                        <DTPOSTED>20251226120000</DTPOSTED>
+                        <TRNAMT>-50.00</TRNAMT>
+                        <MEMO><![CDATA[Ends with </OFX> marker]]></MEMO>
+                    </STMTTRN>
+                    <STMTTRN>
+                        <TRNTYPE>CREDIT</TRNTYPE>
+                        <DTPOSTED>20251227120000</DTPOSTED>
+                        <TRNAMT>1500.00</TRNAMT>
+                    </STMTTRN>
+                </BANKTRANLIST>
+            </STMTRS>
+        </STMTTRNRS>
+    </BANKMSGSRSV1>
+</OFX>"#;
+
+    #[test]
+    fn test_parse_closing_tag_inside_memo() {
+        let transactions = QfxParser::parse(SAMPLE_OFX_CLOSE_IN_MEMO).unwrap();
+        assert_eq!(transactions.len(), 2);
+        assert_eq!(
+            transactions[0].memo,
+            Some("Ends with </OFX> marker".to_string())
+        );
+        assert_eq!(transactions[1].trn_type, "CREDIT");
+    }
+
+    #[test]
+    fn test_parse_sgml_closing_tag_inside_memo() {
+        let transactions = QfxParser::parse(&sgml_close_in_memo()).unwrap();
+        assert_eq!(transactions.len(), 2);
+        assert_eq!(
+            transactions[0].memo,
+            Some("Ends with </OFX> marker".to_string())
+        );
+        assert_eq!(transactions[1].trn_type, "CREDIT");
+    }
+
+    #[rstest]
+    #[case(SAMPLE_OFX_CLOSE_IN_MEMO.to_string())]
+    #[case(sgml_close_in_memo())]
+    fn test_parse_reader_closing_tag_inside_memo(#[case] content: String) {
+        let transactions = QfxParser::parse_reader(content.as_bytes()).unwrap();
+        assert_eq!(transactions.len(), 2);
+        assert_eq!(
+            transactions[0].memo,
+            Some("Ends with </OFX> marker".to_string())
+        );
+        assert_eq!(transactions[1].trn_type, "CREDIT");
+    }
+
+    fn sgml_close_in_memo() -> String {
+        SAMPLE_SGML_QFX
+            .replace("<MEMO>Morning coffee", "<MEMO>Ends with </OFX> marker")
+            .replace(
+                "</STMTTRN>",
+                "</STMTTRN>\n<STMTTRN>\n<TRNTYPE>CREDIT\n<DTPOSTED>20251227\n<TRNAMT>10.00\n</STMTTRN>",
+            )
+    }
+
+    #[test]
+    fn test_parse_reader_excludes_trailing_closing_tag() {
+        let content = format!("{}\n<JUNK>\n</OFX>\n", SAMPLE_SGML_QFX);
+        let reader = content.as_bytes();
+
+        let transactions = QfxParser::parse_reader(reader).unwrap();
+        assert_eq!(transactions.len(), 1);
+    }
+
+    #[rstest]
+    #[case(SAMPLE_XML_QFX)]
+    #[case(SAMPLE_SGML_QFX)]
+    fn test_parse_reader_matches_parse(#[case] content: &str) {
+        let from_reader = QfxParser::parse_reader(content.as_bytes()).unwrap();
+        let from_str = QfxParser::parse(content).unwrap();
+
+        assert_eq!(from_reader.len(), from_str.len());
+        assert_eq!(from_reader[0].trn_type, from_str[0].trn_type);
+        assert_eq!(from_reader[0].amount, from_str[0].amount);
+        assert_eq!(from_reader[0].fitid, from_str[0].fitid);
+    }
+
+    /// Fails any read, to prove a parser stopped before reaching it.
+    struct UnreadableTail;
+
+    impl Read for UnreadableTail {
+        fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("read past </OFX>"))
+        }
+    }
+
+    #[rstest]
+    #[case(SAMPLE_XML_QFX)]
+    #[case(SAMPLE_SGML_QFX)]
+    fn test_parse_reader_stops_at_closing_tag(#[case] sample: &str) {
+        let content = format!("{}\n", sample);
+        let reader = content.as_bytes().chain(UnreadableTail);
+
+        let transactions = QfxParser::parse_reader(reader).unwrap();
+        assert_eq!(transactions.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_statement_reader_uses_options_and_warnings() {
+        let content = SAMPLE_XML_QFX.replace(
+            "<STMTRS>",
+            "<STATUS><CODE>2000</CODE><SEVERITY>WARN</SEVERITY></STATUS><STMTRS>",
+        );
+        let options = QfxOptions {
+            keep_raw_source: true,
+            ..QfxOptions::default()
+        };
+        let mut warnings = Vec::new();
+
+        let statement = QfxParser::parse_statement_reader(content.as_bytes(), &options, &mut |w| {
+            warnings.push(w.clone())
+        })
+        .unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(
+            statement.transactions[0]
+                .raw_source
+                .as_deref()
+                .is_some_and(|raw| raw.starts_with("<STMTTRN>"))
+        );
+    }
+
+    #[test]
+    fn test_parse_reader_ignores_trailing_content() {
+        let content = format!("{}\n<TRAILER>not ofx", SAMPLE_XML_QFX);
+
+        let transactions = QfxParser::parse_reader(content.as_bytes()).unwrap();
+        assert_eq!(transactions.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_statement_ledger_balance() {
+        let content = SAMPLE_SGML_QFX.replace(
+            "</BANKTRANLIST>",
+            "</BANKTRANLIST>\n<LEDGERBAL>\n<BALAMT>1234.56\n<DTASOF>20251231120000\n</LEDGERBAL>",
+        );
+
+        let statement = QfxParser::parse_statement(&content).unwrap();
+        assert_eq!(statement.transactions.len(), 1);
+        assert_eq!(
+            statement.ledger_balance,
+            Some(Decimal::from_str("1234.56").unwrap())
+        );
+        assert_eq!(
+            statement.balance_date.map(String::from).as_deref(),
+            Some("20251231120000")
+        );
+    }
+
+    #[test]
+    fn test_parse_statement_without_ledger_balance() {
+        let statement = QfxParser::parse_statement(SAMPLE_SGML_QFX).unwrap();
+        assert_eq!(statement.transactions.len(), 1);
+        assert_eq!(statement.ledger_balance, None);
+        assert!(statement.balance_date.is_none());
+    }
+
+    #[test]
+    fn test_parse_statement_language_and_default_currency() {
+        let content = SAMPLE_SGML_QFX.replace(
+            "<BANKMSGSRSV1>",
+            "<SIGNONMSGSRSV1>\n<SONRS>\n<LANGUAGE>ENG\n</SONRS>\n</SIGNONMSGSRSV1>\n<BANKMSGSRSV1>",
+        );
+
+        let statement = QfxParser::parse_statement(&content).unwrap();
+        assert_eq!(statement.language.as_deref(), Some("ENG"));
+        assert_eq!(statement.default_currency.as_deref(), Some("USD"));
+
+        let statement = QfxParser::parse_statement(SAMPLE_MONEY_QFX).unwrap();
+        assert_eq!(statement.language.as_deref(), Some("ENG"));
+        assert_eq!(statement.default_currency.as_deref(), Some("USD"));
+    }
+
+    #[rstest]
+    #[case(SAMPLE_SGML_QFX, Some("USD"))]
+    #[case(SAMPLE_XML_QFX, None)]
+    fn test_parse_statement_without_language(
+        #[case] content: &str,
+        #[case] default_currency: Option<&str>,
+    ) {
+        let statement = QfxParser::parse_statement(content).unwrap();
+        assert_eq!(statement.language, None);
+        assert_eq!(statement.default_currency.as_deref(), default_currency);
+    }
+
+    #[test]
+    fn test_parse_empty_curdef_not_backfilled() {
+        let content = SAMPLE_XML_QFX.replace("<STMTRS>", "<STMTRS><CURDEF></CURDEF>");
+
+        let statement = QfxParser::parse_statement(&content).unwrap();
+        assert_eq!(statement.default_currency, None);
+        assert_eq!(statement.transactions[0].currency, None);
+    }
+
+    #[test]
+    fn test_parse_statement_invalid_ledger_balance() {
+        let content = SAMPLE_SGML_QFX.replace(
+            "</BANKTRANLIST>",
+            "</BANKTRANLIST>\n<LEDGERBAL>\n<BALAMT>lots\n</LEDGERBAL>",
+        );
+        let result = QfxParser::parse_statement(&content);
+        assert!(result.unwrap_err().contains("Invalid amount"));
+    }
+
+    #[test]
+    fn test_parse_reader_iter() {
+        let fitids: Vec<_> = QfxParser::parse_reader_iter(SAMPLE_NO_STMTRS_QFX.as_bytes())
+            .unwrap()
+            .map(|transaction| transaction.fitid)
+            .collect();
+        assert_eq!(
+            fitids,
+            [Some("202512260".to_string()), Some("202512270".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_parse_reader_single_line() {
+        let content = SAMPLE_XML_QFX.replace('\n', "");
+
+        let transactions = QfxParser::parse_reader(content.as_bytes()).unwrap();
+        assert_eq!(transactions.len(), 1);
+    }
+
+    #[rstest]
+    #[case("no ofx here", "Missing <OFX> tag")]
+    #[case("<OFX>\n<BANKMSGSRSV1>", "Missing </OFX> tag")]
+    fn test_parse_reader_missing_tags(#[case] content: &str, #[case] expected: &str) {
+        let result = QfxParser::parse_reader(content.as_bytes());
+        assert!(result.unwrap_err().contains(expected));
+    }
+
+    #[test]
+    fn test_parse_without_raw_source_by_default() {
+        let transactions = QfxParser::parse(SAMPLE_XML_QFX).unwrap();
+        assert_eq!(transactions[0].raw_source, None);
+    }
+
+    #[test]
+    fn test_parse_with_options_keep_raw_source() {
+        let options = QfxOptions {
+            keep_raw_source: true,
+            ..QfxOptions::default()
+        };
+        let transactions = QfxParser::parse_with_options(SAMPLE_XML_QFX, &options).unwrap();
+
+        let raw = transactions[0].raw_source.as_deref().unwrap();
+        assert!(raw.starts_with("<STMTTRN>"));
+        assert!(raw.ends_with("</STMTTRN>"));
+        assert!(raw.contains("<FITID>202512260</FITID>"));
+    }
+
+    #[test]
+    fn test_parse_with_options_keep_raw_source_sgml() {
+        let options = QfxOptions {
+            keep_raw_source: true,
+            ..QfxOptions::default()
+        };
+        let transactions = QfxParser::parse_with_options(SAMPLE_SGML_QFX, &options).unwrap();
+
+        let raw = transactions[0].raw_source.as_deref().unwrap();
+        assert!(raw.contains("<TRNAMT>-50.00</TRNAMT>"));
+    }
+
+    #[test]
+    fn test_parse_keep_raw_source_cc_before_bank() {
+        let options = QfxOptions {
+            keep_raw_source: true,
+            collect_row_errors: true,
+            ..QfxOptions::default()
+        };
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<OFX>
+    <CREDITCARDMSGSRSV1>
+        <CCSTMTTRNRS>
+            <CCSTMTRS>
+                <BANKTRANLIST>
+                    <STMTTRN>
+                        <TRNTYPE>DEBIT</TRNTYPE>
+                        <DTPOSTED>20251226</DTPOSTED>
+                        <TRNAMT>-20.00</TRNAMT>
+                        <FITID>CC1</FITID>
+                    </STMTTRN>
+                </BANKTRANLIST>
+            </CCSTMTRS>
+        </CCSTMTTRNRS>
+    </CREDITCARDMSGSRSV1>
+    <BANKMSGSRSV1>
+        <STMTTRNRS>
+            <STMTRS>
+                <BANKTRANLIST>
+                    <STMTTRN>
+                        <TRNTYPE>DEBIT</TRNTYPE>
+                        <DTPOSTED>20251226</DTPOSTED>
+                        <TRNAMT>abc</TRNAMT>
+                        <FITID>BANK1</FITID>
+                    </STMTTRN>
+                    <STMTTRN>
+                        <TRNTYPE>CREDIT</TRNTYPE>
+                        <DTPOSTED>20251227</DTPOSTED>
+                        <TRNAMT>100.00</TRNAMT>
+                        <FITID>BANK2</FITID>
+                    </STMTTRN>
+                </BANKTRANLIST>
+            </STMTRS>
+        </STMTTRNRS>
+        <STMTTRNRS>
+            <STMTRS>
+                <BANKTRANLIST>
+                    <STMTTRN>
+                        <TRNTYPE>CREDIT</TRNTYPE>
+                        <DTPOSTED>20251228</DTPOSTED>
+                        <TRNAMT>5.00</TRNAMT>
+                        <FITID>BANK3</FITID>
+                    </STMTTRN>
+                </BANKTRANLIST>
+            </STMTRS>
+        </STMTTRNRS>
+    </BANKMSGSRSV1>
+</OFX>"#;
+
+        let statement =
+            QfxParser::parse_statement_with_warnings(content, &options, &mut |_| {}).unwrap();
+        assert_eq!(statement.row_errors.len(), 1);
+        assert_eq!(statement.transactions.len(), 3);
+        for transaction in &statement.transactions {
+            let fitid = transaction.fitid.as_deref().unwrap();
+            let raw = transaction.raw_source.as_deref().unwrap();
+            assert!(raw.contains(&format!("<FITID>{fitid}</FITID>")), "{fitid}");
+        }
+    }
+
+    #[test]
+    fn test_extract_stmttrn_blocks_preserves_order() {
+        let content =
+            "<OFX><STMTTRN><FITID>1</FITID></STMTTRN><STMTTRN><FITID>2</FITID></STMTTRN></OFX>";
+
+        let blocks = extract_stmttrn_blocks(content);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0], "<STMTTRN><FITID>1</FITID></STMTTRN>");
+        assert_eq!(blocks[1], "<STMTTRN><FITID>2</FITID></STMTTRN>");
+    }
+
     #[test]
     fn test_convert_sgml_to_xml_basic() {
         let sgml = r#"OFXHEADER:100
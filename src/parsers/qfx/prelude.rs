@@ -1,2 +1,3 @@
-pub use super::dto::QfxTransaction;
+pub use super::dto::{QfxStatement, QfxTransaction};
+pub use super::options::QfxOptions;
 pub use super::parser::QfxParser;
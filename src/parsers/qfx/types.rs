@@ -1,10 +1,20 @@
-use crate::errors::StatementParseError;
-use chrono::NaiveDate;
+use crate::errors::{StatementParseError, StatementResult};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime};
 use serde::{Deserialize, Deserializer, Serialize};
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
 pub struct QfxDate(String);
 
+impl QfxDate {
+    pub fn from_naive(date: NaiveDate) -> Self {
+        Self(date.format("%Y%m%d000000").to_string())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
 impl<'de> Deserialize<'de> for QfxDate {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -26,11 +36,77 @@ impl From<&str> for QfxDate {
     }
 }
 
-impl TryFrom<QfxDate> for NaiveDate {
-    type Error = StatementParseError;
+impl From<QfxDate> for String {
+    fn from(date: QfxDate) -> Self {
+        date.0
+    }
+}
 
-    fn try_from(date_str: QfxDate) -> Result<Self, Self::Error> {
-        let clean = date_str
+impl QfxDate {
+    /// Like `NaiveDate::try_from`, but a day past the end of its month is
+    /// clamped to the month's last day (e.g. `20250431` becomes April 30).
+    /// The flag is `true` when the day was clamped.
+    pub fn to_naive_date_clamped(&self) -> StatementResult<(NaiveDate, bool)> {
+        let (year, month, day) = self.ymd()?;
+        if let Some(date) = NaiveDate::from_ymd_opt(year, month, day) {
+            return Ok((date, false));
+        }
+
+        let first_of_month = NaiveDate::from_ymd_opt(year, month, 1)
+            .filter(|_| day > 28)
+            .ok_or(StatementParseError::QfxDateInvalidFormat)?;
+        let last_day = first_of_month
+            .checked_add_months(chrono::Months::new(1))
+            .and_then(|next_month| next_month.pred_opt())
+            .ok_or(StatementParseError::QfxDateInvalidFormat)?;
+        Ok((last_day, true))
+    }
+
+    /// Parses the full timestamp, including the time of day and the
+    /// `[-3:GMT]`-style offset. A missing time is midnight and a missing
+    /// offset is UTC.
+    pub fn to_datetime(&self) -> StatementResult<DateTime<FixedOffset>> {
+        let (year, month, day) = self.ymd()?;
+        let date = NaiveDate::from_ymd_opt(year, month, day)
+            .ok_or(StatementParseError::QfxDateInvalidFormat)?;
+
+        let (timestamp, zone) = self.0.split_once('[').unwrap_or((&self.0, ""));
+        let (digits, fraction) = timestamp
+            .trim()
+            .split_once('.')
+            .unwrap_or((timestamp.trim(), ""));
+        let time = match &digits[8..] {
+            "" => Ok(NaiveTime::MIN),
+            clock if fraction.is_empty() => NaiveTime::parse_from_str(clock, "%H%M%S"),
+            clock => NaiveTime::parse_from_str(&format!("{clock}.{fraction}"), "%H%M%S%.f"),
+        }
+        .map_err(|_| StatementParseError::QfxDateInvalidFormat)?;
+
+        let hours = zone
+            .trim_end_matches(']')
+            .split(':')
+            .next()
+            .unwrap_or("")
+            .trim();
+        let offset_seconds = if hours.is_empty() {
+            0
+        } else {
+            let hours: f64 = hours
+                .parse()
+                .map_err(|_| StatementParseError::QfxDateInvalidFormat)?;
+            (hours * 3600.0).round() as i32
+        };
+        let offset = FixedOffset::east_opt(offset_seconds)
+            .ok_or(StatementParseError::QfxDateInvalidFormat)?;
+
+        date.and_time(time)
+            .and_local_timezone(offset)
+            .single()
+            .ok_or(StatementParseError::QfxDateInvalidFormat)
+    }
+
+    fn ymd(&self) -> StatementResult<(i32, u32, u32)> {
+        let clean = self
             .0
             .split(&['[', '.'][..])
             .next()
@@ -51,6 +127,15 @@ impl TryFrom<QfxDate> for NaiveDate {
             .parse()
             .map_err(|_| StatementParseError::QfxDateInvalidFormat)?;
 
+        Ok((year, month, day))
+    }
+}
+
+impl TryFrom<QfxDate> for NaiveDate {
+    type Error = StatementParseError;
+
+    fn try_from(date_str: QfxDate) -> Result<Self, Self::Error> {
+        let (year, month, day) = date_str.ymd()?;
         NaiveDate::from_ymd_opt(year, month, day).ok_or(StatementParseError::QfxDateInvalidFormat)
     }
 }
@@ -93,6 +178,63 @@ mod tests {
         ));
     }
 
+    #[rstest]
+    #[case("20250431", NaiveDate::from_ymd_opt(2025, 4, 30).unwrap(), true)]
+    #[case("20250229120000[-5:EST]", NaiveDate::from_ymd_opt(2025, 2, 28).unwrap(), true)]
+    #[case("20240231", NaiveDate::from_ymd_opt(2024, 2, 29).unwrap(), true)]
+    #[case("20251299", NaiveDate::from_ymd_opt(2025, 12, 31).unwrap(), true)]
+    #[case("20250430", NaiveDate::from_ymd_opt(2025, 4, 30).unwrap(), false)]
+    fn test_qfx_date_clamped(
+        #[case] date_str: &str,
+        #[case] expected: NaiveDate,
+        #[case] clamped: bool,
+    ) {
+        let date = QfxDate::from(date_str);
+        assert_eq!(date.to_naive_date_clamped().unwrap(), (expected, clamped));
+    }
+
+    #[rstest]
+    #[case("20251301")]
+    #[case("20250400")]
+    #[case("short")]
+    fn test_qfx_date_clamped_invalid(#[case] date_str: &str) {
+        let date = QfxDate::from(date_str);
+        assert!(matches!(
+            date.to_naive_date_clamped(),
+            Err(StatementParseError::QfxDateInvalidFormat)
+        ));
+    }
+
+    #[rstest]
+    #[case("20251226120000[0:GMT]", "2025-12-26T12:00:00+00:00")]
+    #[case("20251226083015[-3:BRT]", "2025-12-26T08:30:15-03:00")]
+    #[case("20251226083015[+5.5:IST]", "2025-12-26T08:30:15+05:30")]
+    #[case("20251226083015.250[-5:EST]", "2025-12-26T08:30:15.250-05:00")]
+    #[case("20251226120000", "2025-12-26T12:00:00+00:00")]
+    #[case("20251226[0:GMT]", "2025-12-26T00:00:00+00:00")]
+    #[case("20251226.000", "2025-12-26T00:00:00+00:00")]
+    #[case("20251226", "2025-12-26T00:00:00+00:00")]
+    fn test_qfx_date_to_datetime(#[case] date_str: &str, #[case] expected: &str) {
+        let date = QfxDate::from(date_str);
+        assert_eq!(
+            date.to_datetime().unwrap(),
+            DateTime::parse_from_rfc3339(expected).unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case("20251226120000[abc:GMT]")]
+    #[case("20251226126000")]
+    #[case("202512261200")]
+    #[case("20251301120000")]
+    fn test_qfx_date_to_datetime_invalid(#[case] date_str: &str) {
+        let date = QfxDate::from(date_str);
+        assert!(matches!(
+            date.to_datetime(),
+            Err(StatementParseError::QfxDateInvalidFormat)
+        ));
+    }
+
     #[test]
     fn test_qfx_date_from_string() {
         let date = QfxDate::from("20251226120000".to_string());
@@ -107,6 +249,18 @@ mod tests {
         assert_eq!(parsed, NaiveDate::from_ymd_opt(2025, 12, 25).unwrap());
     }
 
+    #[rstest]
+    #[case(NaiveDate::from_ymd_opt(2025, 12, 26).unwrap(), "20251226000000")]
+    #[case(NaiveDate::from_ymd_opt(2025, 1, 5).unwrap(), "20250105000000")]
+    #[case(NaiveDate::from_ymd_opt(999, 1, 1).unwrap(), "09990101000000")]
+    fn test_qfx_date_from_naive(#[case] date: NaiveDate, #[case] expected: &str) {
+        let qfx_date = QfxDate::from_naive(date);
+        assert_eq!(qfx_date.0, expected);
+
+        let parsed: NaiveDate = qfx_date.try_into().unwrap();
+        assert_eq!(parsed, date);
+    }
+
     #[test]
     fn test_qfx_date_serialization() {
         let date = QfxDate::from("20251226120000[0:GMT]");
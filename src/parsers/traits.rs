@@ -1,7 +1,93 @@
+use std::io::Read;
+
+use crate::types::Transaction;
+
 pub trait Parser {
     type Output;
 
     fn parse(content: &str) -> Result<Vec<Self::Output>, String>;
 
     fn is_supported(filename: Option<&str>, content: &str) -> bool;
+
+    /// How sure the parser is that it can read `content`, from 0.0 to 1.0.
+    /// Used by `FileFormat::detect_scored` to rank formats. The default maps
+    /// `is_supported` to 1.0 or 0.0.
+    fn confidence(filename: Option<&str>, content: &str) -> f32 {
+        if Self::is_supported(filename, content) {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    /// Parses from `reader` instead of a string.
+    ///
+    /// The default reads everything into memory and calls `parse`. Formats
+    /// that can skip or stream input override it; see each parser for how
+    /// much it buffers.
+    fn parse_reader<R: Read>(mut reader: R) -> Result<Vec<Self::Output>, String> {
+        let mut content = String::new();
+        reader
+            .read_to_string(&mut content)
+            .map_err(|e| format!("Read error: {}", e))?;
+        Self::parse(&content)
+    }
+
+    /// Iterator form of `parse_reader`. Unless a parser overrides it, all
+    /// input is read and parsed before the first item is yielded.
+    fn parse_reader_iter<R: Read>(reader: R) -> Result<impl Iterator<Item = Self::Output>, String> {
+        Self::parse_reader(reader).map(Vec::into_iter)
+    }
+}
+
+/// Object-safe counterpart of [`Parser`] for formats registered at runtime
+/// with `ParserBuilder::with_parser`. It produces `Transaction`s directly.
+pub trait DynParser {
+    fn parse(&self, content: &str) -> Result<Vec<Transaction>, String>;
+
+    fn is_supported(&self, filename: Option<&str>, content: &str) -> bool;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct LineParser;
+
+    impl Parser for LineParser {
+        type Output = String;
+
+        fn parse(content: &str) -> Result<Vec<String>, String> {
+            Ok(content.lines().map(str::to_string).collect())
+        }
+
+        fn is_supported(_filename: Option<&str>, _content: &str) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_default_confidence() {
+        assert_eq!(LineParser::confidence(None, "anything"), 1.0);
+    }
+
+    #[test]
+    fn test_default_parse_reader() {
+        let lines = LineParser::parse_reader("a\nb\n".as_bytes()).unwrap();
+        assert_eq!(lines, ["a", "b"]);
+    }
+
+    #[test]
+    fn test_default_parse_reader_iter() {
+        let mut lines = LineParser::parse_reader_iter("a\nb\n".as_bytes()).unwrap();
+        assert_eq!(lines.next().as_deref(), Some("a"));
+        assert_eq!(lines.next().as_deref(), Some("b"));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn test_default_parse_reader_invalid_utf8() {
+        let result = LineParser::parse_reader(&[0xff, 0xfe][..]);
+        assert!(result.unwrap_err().starts_with("Read error"));
+    }
 }
@@ -1,43 +1,372 @@
-use crate::{builder::ParsedTransaction, errors::StatementParseError, parsers::qfx::prelude::*};
+use crate::parsers::qfx::types::QfxDate;
+use crate::{
+    builder::ParsedTransaction,
+    errors::{StatementParseError, StatementResult},
+    parsers::{camt::prelude::*, qfx::prelude::*},
+};
 use chrono::NaiveDate;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Equality and hashing cover every field. Amounts compare numerically, so
+/// `50.0` and `50.00` are equal and hash the same.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Transaction {
     pub date: NaiveDate,
+    pub user_date: Option<NaiveDate>,
     pub amount: Decimal,
     pub payee: Option<String>,
+    pub normalized_payee: Option<String>,
     pub transaction_type: String,
     pub fitid: Option<String>,
+    pub check_number: Option<String>,
     pub status: Option<String>,
     pub memo: Option<String>,
+    pub account_id: Option<String>,
+    pub currency: Option<String>,
+    pub original_currency: Option<String>,
+    pub original_amount: Option<Decimal>,
+    pub raw_source: Option<String>,
 }
 
-impl TryFrom<ParsedTransaction> for Transaction {
-    type Error = StatementParseError;
+/// Partial update for [`Transaction::with_overrides`]. `None` keeps the
+/// existing value.
+#[derive(Debug, Clone, Default)]
+pub struct TransactionOverrides {
+    pub date: Option<NaiveDate>,
+    pub user_date: Option<NaiveDate>,
+    pub amount: Option<Decimal>,
+    pub payee: Option<String>,
+    pub normalized_payee: Option<String>,
+    pub transaction_type: Option<String>,
+    pub fitid: Option<String>,
+    pub check_number: Option<String>,
+    pub status: Option<String>,
+    pub memo: Option<String>,
+    pub account_id: Option<String>,
+    pub currency: Option<String>,
+    pub original_currency: Option<String>,
+    pub original_amount: Option<Decimal>,
+    pub raw_source: Option<String>,
+}
 
-    fn try_from(parsed: ParsedTransaction) -> Result<Self, Self::Error> {
-        match parsed {
-            ParsedTransaction::Qfx(qfx) => qfx.try_into(),
+/// Predicate over parsed transactions, usable with
+/// `ParserBuilder::filter` or on its own via [`TransactionFilter::matches`].
+///
+/// Ranges are inclusive at both ends. `None` and an empty `types` match
+/// everything.
+#[derive(Debug, Clone, Default)]
+pub struct TransactionFilter {
+    pub date_from: Option<NaiveDate>,
+    pub date_to: Option<NaiveDate>,
+    pub min_amount: Option<Decimal>,
+    pub max_amount: Option<Decimal>,
+    pub types: Vec<TransactionType>,
+}
+
+impl TransactionFilter {
+    pub fn matches(&self, transaction: &Transaction) -> bool {
+        self.date_from.is_none_or(|from| transaction.date >= from)
+            && self.date_to.is_none_or(|to| transaction.date <= to)
+            && self.min_amount.is_none_or(|min| transaction.amount >= min)
+            && self.max_amount.is_none_or(|max| transaction.amount <= max)
+            && (self.types.is_empty() || self.types.contains(&transaction.kind()))
+    }
+}
+
+/// Order of the transactions returned by `ParserBuilder::sort`. Sorting is
+/// stable, so transactions on the same date keep their file order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    /// Keep the order of the file.
+    #[default]
+    None,
+    DateAsc,
+    DateDesc,
+}
+
+/// OFX `TRNTYPE` values. Codes outside the spec are kept in `Other`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TransactionType {
+    Credit,
+    Debit,
+    Interest,
+    Dividend,
+    Fee,
+    ServiceCharge,
+    Deposit,
+    Atm,
+    PointOfSale,
+    Transfer,
+    Check,
+    Payment,
+    Cash,
+    DirectDeposit,
+    DirectDebit,
+    RepeatPayment,
+    Hold,
+    Other(String),
+}
+
+impl TransactionType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            TransactionType::Credit => "CREDIT",
+            TransactionType::Debit => "DEBIT",
+            TransactionType::Interest => "INT",
+            TransactionType::Dividend => "DIV",
+            TransactionType::Fee => "FEE",
+            TransactionType::ServiceCharge => "SRVCHG",
+            TransactionType::Deposit => "DEP",
+            TransactionType::Atm => "ATM",
+            TransactionType::PointOfSale => "POS",
+            TransactionType::Transfer => "XFER",
+            TransactionType::Check => "CHECK",
+            TransactionType::Payment => "PAYMENT",
+            TransactionType::Cash => "CASH",
+            TransactionType::DirectDeposit => "DIRECTDEP",
+            TransactionType::DirectDebit => "DIRECTDEBIT",
+            TransactionType::RepeatPayment => "REPEATPMT",
+            TransactionType::Hold => "HOLD",
+            TransactionType::Other(code) => code,
+        }
+    }
+
+    /// `Some(true)` for types that always take money out (`DEBIT`, `FEE`,
+    /// `ATM`, `POS`), `Some(false)` for ones that always bring it in
+    /// (`CREDIT`, `DEP`), and `None` when it depends on the transaction.
+    pub fn is_outflow(&self) -> Option<bool> {
+        match self {
+            TransactionType::Debit
+            | TransactionType::Fee
+            | TransactionType::Atm
+            | TransactionType::PointOfSale => Some(true),
+            TransactionType::Credit | TransactionType::Deposit => Some(false),
+            _ => None,
         }
     }
 }
 
-impl TryFrom<QfxTransaction> for Transaction {
-    type Error = StatementParseError;
+impl From<&str> for TransactionType {
+    fn from(code: &str) -> Self {
+        match code.trim().to_ascii_uppercase().as_str() {
+            "CREDIT" => TransactionType::Credit,
+            "DEBIT" => TransactionType::Debit,
+            "INT" => TransactionType::Interest,
+            "DIV" => TransactionType::Dividend,
+            "FEE" => TransactionType::Fee,
+            "SRVCHG" => TransactionType::ServiceCharge,
+            "DEP" => TransactionType::Deposit,
+            "ATM" => TransactionType::Atm,
+            "POS" => TransactionType::PointOfSale,
+            "XFER" => TransactionType::Transfer,
+            "CHECK" => TransactionType::Check,
+            "PAYMENT" => TransactionType::Payment,
+            "CASH" => TransactionType::Cash,
+            "DIRECTDEP" => TransactionType::DirectDeposit,
+            "DIRECTDEBIT" => TransactionType::DirectDebit,
+            "REPEATPMT" => TransactionType::RepeatPayment,
+            "HOLD" => TransactionType::Hold,
+            _ => TransactionType::Other(code.to_string()),
+        }
+    }
+}
 
-    fn try_from(stmt: QfxTransaction) -> Result<Self, Self::Error> {
+impl Transaction {
+    pub fn with_overrides(self, overrides: TransactionOverrides) -> Transaction {
+        Transaction {
+            date: overrides.date.unwrap_or(self.date),
+            user_date: overrides.user_date.or(self.user_date),
+            amount: overrides.amount.unwrap_or(self.amount),
+            payee: overrides.payee.or(self.payee),
+            normalized_payee: overrides.normalized_payee.or(self.normalized_payee),
+            transaction_type: overrides.transaction_type.unwrap_or(self.transaction_type),
+            fitid: overrides.fitid.or(self.fitid),
+            check_number: overrides.check_number.or(self.check_number),
+            status: overrides.status.or(self.status),
+            memo: overrides.memo.or(self.memo),
+            account_id: overrides.account_id.or(self.account_id),
+            currency: overrides.currency.or(self.currency),
+            original_currency: overrides.original_currency.or(self.original_currency),
+            original_amount: overrides.original_amount.or(self.original_amount),
+            raw_source: overrides.raw_source.or(self.raw_source),
+        }
+    }
+
+    /// The row `write_csv` emits, in `Date,Type,Description,Amount,FITID,Memo`
+    /// order. Missing values are empty.
+    pub fn to_csv_record(&self) -> [String; 6] {
+        [
+            self.date.format("%Y-%m-%d").to_string(),
+            self.transaction_type.clone(),
+            self.payee.clone().unwrap_or_default(),
+            self.amount.to_string(),
+            self.fitid.clone().unwrap_or_default(),
+            self.memo.clone().unwrap_or_default(),
+        ]
+    }
+
+    /// The `normalized_payee` field when the builder filled it in, else
+    /// `payee` cleaned up with [`normalize_payee`]. `payee` is left as is;
+    /// `None` when there is no payee or nothing survives the cleanup.
+    ///
+    /// [`normalize_payee`]: crate::analysis::normalize_payee
+    pub fn normalized_payee(&self) -> Option<String> {
+        self.normalized_payee
+            .clone()
+            .or_else(|| self.payee.as_deref().map(crate::analysis::normalize_payee))
+            .filter(|payee| !payee.is_empty())
+    }
+
+    /// `transaction_type` mapped to a [`TransactionType`].
+    pub fn kind(&self) -> TransactionType {
+        TransactionType::from(self.transaction_type.as_str())
+    }
+
+    /// Key for `sort_by_key`: date, then amount, then FITID, all ascending.
+    ///
+    /// Amounts compare numerically, so `-50.00` sorts before `10.00` and
+    /// `50.0` ties with `50.00`. A missing FITID is treated as the empty
+    /// string and so sorts before any present FITID on the same date and
+    /// amount. FITIDs compare lexicographically, not numerically.
+    pub fn sort_key(&self) -> (NaiveDate, Decimal, String) {
+        (
+            self.date,
+            self.amount,
+            self.fitid.clone().unwrap_or_default(),
+        )
+    }
+
+    /// Like `TryFrom<ParsedTransaction>`, but dates are read by `parse_date`.
+    pub(crate) fn from_parsed_with_date_parser(
+        parsed: ParsedTransaction,
+        parse_date: &mut dyn FnMut(&str) -> StatementResult<NaiveDate>,
+    ) -> StatementResult<Self> {
+        match parsed {
+            ParsedTransaction::Qfx(qfx) => Self::from_qfx(qfx, |date| parse_date(date.as_str())),
+            ParsedTransaction::Camt(camt) => Self::from_camt(camt, |date| parse_date(date)),
+            ParsedTransaction::Custom(transaction) => Ok(transaction),
+        }
+    }
+
+    fn from_qfx(
+        stmt: QfxTransaction,
+        mut parse_date: impl FnMut(QfxDate) -> StatementResult<NaiveDate>,
+    ) -> StatementResult<Self> {
         Ok(Transaction {
-            date: stmt.dt_posted.try_into()?,
+            date: parse_date(stmt.dt_posted)?,
+            // DTUSER is auxiliary, so an unreadable one is dropped rather
+            // than failing the transaction.
+            user_date: stmt.dt_user.and_then(|date| parse_date(date).ok()),
             amount: stmt.amount,
             payee: stmt.name,
+            normalized_payee: None,
             transaction_type: stmt.trn_type,
             fitid: stmt.fitid,
+            check_number: stmt.check_num,
             status: None,
             memo: stmt.memo,
+            account_id: stmt.account_id,
+            currency: stmt.currency,
+            original_currency: stmt.original_currency,
+            original_amount: stmt.original_amount,
+            raw_source: stmt.raw_source,
         })
     }
+
+    fn from_camt(
+        camt: CamtTransaction,
+        mut parse_date: impl FnMut(&str) -> StatementResult<NaiveDate>,
+    ) -> StatementResult<Self> {
+        let (amount, transaction_type) = if camt.is_debit() {
+            (-camt.amount, "DEBIT")
+        } else {
+            (camt.amount, "CREDIT")
+        };
+        Ok(Transaction {
+            date: parse_date(&camt.booking_date)?,
+            // Like DTUSER, an unreadable value date is dropped.
+            user_date: camt
+                .value_date
+                .as_deref()
+                .and_then(|date| parse_date(date).ok()),
+            amount,
+            payee: camt.description,
+            normalized_payee: None,
+            transaction_type: transaction_type.to_string(),
+            fitid: camt.reference,
+            check_number: None,
+            status: None,
+            memo: None,
+            account_id: camt.account_id,
+            currency: camt.currency,
+            original_currency: None,
+            original_amount: None,
+            raw_source: None,
+        })
+    }
+}
+
+/// Reads the `YYYY-MM-DD` prefix of a camt `Dt` or `DtTm`.
+fn parse_camt_date(date: &str) -> StatementResult<NaiveDate> {
+    date.get(..10)
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+        .ok_or(StatementParseError::CamtDateInvalidFormat)
+}
+
+impl TryFrom<ParsedTransaction> for Transaction {
+    type Error = StatementParseError;
+
+    fn try_from(parsed: ParsedTransaction) -> Result<Self, Self::Error> {
+        match parsed {
+            ParsedTransaction::Qfx(qfx) => qfx.try_into(),
+            ParsedTransaction::Camt(camt) => camt.try_into(),
+            ParsedTransaction::Custom(transaction) => Ok(transaction),
+        }
+    }
+}
+
+impl TryFrom<QfxTransaction> for Transaction {
+    type Error = StatementParseError;
+
+    fn try_from(stmt: QfxTransaction) -> Result<Self, Self::Error> {
+        Self::from_qfx(stmt, NaiveDate::try_from)
+    }
+}
+
+/// Signs the amount from `CdtDbtInd`: `DBIT` entries become negative.
+impl TryFrom<CamtTransaction> for Transaction {
+    type Error = StatementParseError;
+
+    fn try_from(camt: CamtTransaction) -> Result<Self, Self::Error> {
+        Self::from_camt(camt, parse_camt_date)
+    }
+}
+
+/// Rebuilds the QFX DTO, e.g. for writing a statement back out.
+///
+/// Lossy: `status` and `normalized_payee` have no QFX home and are dropped,
+/// `ref_num` is left empty, and dates are written as midnight since
+/// `Transaction` keeps no time.
+impl From<Transaction> for QfxTransaction {
+    fn from(transaction: Transaction) -> Self {
+        QfxTransaction {
+            trn_type: transaction.transaction_type,
+            dt_posted: QfxDate::from_naive(transaction.date),
+            dt_user: transaction.user_date.map(QfxDate::from_naive),
+            amount: transaction.amount,
+            fitid: transaction.fitid,
+            name: transaction.payee,
+            memo: transaction.memo,
+            check_num: transaction.check_number,
+            ref_num: None,
+            account_id: transaction.account_id,
+            currency: transaction.currency,
+            original_currency: transaction.original_currency,
+            original_amount: transaction.original_amount,
+            raw_source: transaction.raw_source,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -46,19 +375,139 @@ mod tests {
     use chrono::NaiveDate;
     use rstest::rstest;
     use rust_decimal::Decimal;
-    use std::str::FromStr;
+    use std::{collections::HashSet, str::FromStr};
 
     fn create_test_qfx_transaction() -> QfxTransaction {
         QfxTransaction {
             trn_type: "DEBIT".to_string(),
             dt_posted: "20251226120000".into(),
+            dt_user: None,
             amount: Decimal::from_str("-50.00").unwrap(),
             fitid: Some("202512260".to_string()),
             name: Some("Test Payee".to_string()),
             memo: Some("Test memo".to_string()),
+            check_num: None,
+            ref_num: None,
+            account_id: None,
+            currency: None,
+            original_currency: None,
+            original_amount: None,
+            raw_source: None,
         }
     }
 
+    #[test]
+    fn test_transaction_hash_set_dedup() {
+        let transaction = Transaction::try_from(create_test_qfx_transaction()).unwrap();
+        let mut rescaled = transaction.clone();
+        rescaled.amount = Decimal::from_str("-50.0").unwrap();
+
+        let set: HashSet<Transaction> = [transaction.clone(), transaction.clone(), rescaled]
+            .into_iter()
+            .collect();
+        assert_eq!(set.len(), 1);
+
+        let mut other = transaction.clone();
+        other.memo = None;
+        assert_ne!(transaction, other);
+    }
+
+    #[rstest]
+    #[case(
+        Some("SQ *COFFEE SHOP      #1234 SEATTLE"),
+        None,
+        Some("COFFEE SHOP SEATTLE")
+    )]
+    #[case(Some("#1234"), None, None)]
+    #[case(Some("SQ *"), None, None)]
+    #[case(None, None, None)]
+    #[case(Some("SQ *COFFEE SHOP"), Some("Coffee"), Some("Coffee"))]
+    fn test_transaction_normalized_payee(
+        #[case] payee: Option<&str>,
+        #[case] normalized: Option<&str>,
+        #[case] expected: Option<&str>,
+    ) {
+        let mut transaction = Transaction::try_from(create_test_qfx_transaction()).unwrap();
+        transaction.payee = payee.map(str::to_string);
+        transaction.normalized_payee = normalized.map(str::to_string);
+
+        assert_eq!(transaction.normalized_payee().as_deref(), expected);
+        assert_eq!(transaction.payee.as_deref(), payee);
+    }
+
+    #[rstest]
+    #[case("DBIT", "2025-12-26", "-50.00", "DEBIT")]
+    #[case("CRDT", "2025-12-26T09:30:00+01:00", "50.00", "CREDIT")]
+    fn test_transaction_from_camt_transaction(
+        #[case] credit_debit: &str,
+        #[case] booking_date: &str,
+        #[case] expected_amount: &str,
+        #[case] expected_type: &str,
+    ) {
+        let camt = CamtTransaction {
+            amount: Decimal::from_str("50.00").unwrap(),
+            currency: Some("EUR".to_string()),
+            credit_debit: credit_debit.to_string(),
+            booking_date: booking_date.to_string(),
+            value_date: Some("2025-12-27".to_string()),
+            description: Some("Coffee Shop".to_string()),
+            reference: Some("REF-001".to_string()),
+            account_id: Some("DE89370400440532013000".to_string()),
+        };
+
+        let transaction = Transaction::try_from(camt).unwrap();
+        assert_eq!(
+            transaction.date,
+            NaiveDate::from_ymd_opt(2025, 12, 26).unwrap()
+        );
+        assert_eq!(
+            transaction.user_date,
+            Some(NaiveDate::from_ymd_opt(2025, 12, 27).unwrap())
+        );
+        assert_eq!(
+            transaction.amount,
+            Decimal::from_str(expected_amount).unwrap()
+        );
+        assert_eq!(transaction.transaction_type, expected_type);
+        assert_eq!(transaction.payee.as_deref(), Some("Coffee Shop"));
+        assert_eq!(transaction.fitid.as_deref(), Some("REF-001"));
+        assert_eq!(transaction.currency.as_deref(), Some("EUR"));
+    }
+
+    #[test]
+    fn test_transaction_from_camt_invalid_date() {
+        let camt = CamtTransaction {
+            amount: Decimal::ONE,
+            currency: None,
+            credit_debit: "CRDT".to_string(),
+            booking_date: "26.12.2025".to_string(),
+            value_date: None,
+            description: None,
+            reference: None,
+            account_id: None,
+        };
+        assert!(matches!(
+            Transaction::try_from(camt),
+            Err(StatementParseError::CamtDateInvalidFormat)
+        ));
+    }
+
+    #[test]
+    fn test_transaction_from_camt_invalid_value_date() {
+        let camt = CamtTransaction {
+            amount: Decimal::ONE,
+            currency: None,
+            credit_debit: "CRDT".to_string(),
+            booking_date: "2025-12-26".to_string(),
+            value_date: Some("soon".to_string()),
+            description: None,
+            reference: None,
+            account_id: None,
+        };
+        let transaction = Transaction::try_from(camt).unwrap();
+        assert_eq!(transaction.user_date, None);
+    }
+
     #[rstest]
     #[case(
         "DEBIT",
@@ -91,10 +540,18 @@ mod tests {
         let qfx = QfxTransaction {
             trn_type: trn_type.to_string(),
             dt_posted: dt_posted.into(),
+            dt_user: None,
             amount: Decimal::from_str(amount).unwrap(),
             fitid: fitid.clone(),
             name: name.clone(),
             memo: memo.clone(),
+            check_num: None,
+            ref_num: None,
+            account_id: None,
+            currency: None,
+            original_currency: None,
+            original_amount: None,
+            raw_source: None,
         };
 
         let result: Result<Transaction, _> = qfx.try_into();
@@ -126,6 +583,277 @@ mod tests {
         assert_eq!(transaction.amount, Decimal::from_str("-50.00").unwrap());
     }
 
+    #[test]
+    fn test_transaction_from_qfx_transaction_account_id() {
+        let qfx = QfxTransaction {
+            account_id: Some("XXXXXXXXXXXX1234".to_string()),
+            ..create_test_qfx_transaction()
+        };
+
+        let transaction: Transaction = qfx.try_into().unwrap();
+        assert_eq!(transaction.account_id, Some("XXXXXXXXXXXX1234".to_string()));
+    }
+
+    #[test]
+    fn test_transaction_from_qfx_transaction_check_number() {
+        let qfx = QfxTransaction {
+            trn_type: "CHECK".to_string(),
+            check_num: Some("1234".to_string()),
+            ref_num: Some("REF-77".to_string()),
+            ..create_test_qfx_transaction()
+        };
+
+        let transaction: Transaction = qfx.try_into().unwrap();
+        assert_eq!(transaction.check_number, Some("1234".to_string()));
+
+        let qfx = QfxTransaction::from(transaction);
+        assert_eq!(qfx.check_num, Some("1234".to_string()));
+        assert_eq!(qfx.ref_num, None);
+    }
+
+    #[rstest]
+    #[case(None, None)]
+    #[case(Some("20251224"), Some(NaiveDate::from_ymd_opt(2025, 12, 24).unwrap()))]
+    fn test_transaction_from_qfx_transaction_user_date(
+        #[case] dt_user: Option<&str>,
+        #[case] expected: Option<NaiveDate>,
+    ) {
+        let qfx = QfxTransaction {
+            dt_user: dt_user.map(Into::into),
+            ..create_test_qfx_transaction()
+        };
+
+        let transaction: Transaction = qfx.try_into().unwrap();
+        assert_eq!(transaction.user_date, expected);
+    }
+
+    #[test]
+    fn test_transaction_from_qfx_transaction_invalid_user_date() {
+        let qfx = QfxTransaction {
+            dt_user: Some("invalid".into()),
+            ..create_test_qfx_transaction()
+        };
+
+        let transaction: Transaction = qfx.try_into().unwrap();
+        assert_eq!(transaction.user_date, None);
+        assert_eq!(
+            transaction.date,
+            NaiveDate::from_ymd_opt(2025, 12, 26).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_transaction_from_qfx_transaction_raw_source() {
+        let qfx = QfxTransaction {
+            raw_source: Some("<STMTTRN></STMTTRN>".to_string()),
+            ..create_test_qfx_transaction()
+        };
+
+        let transaction: Transaction = qfx.try_into().unwrap();
+        assert_eq!(
+            transaction.raw_source,
+            Some("<STMTTRN></STMTTRN>".to_string())
+        );
+    }
+
+    #[test]
+    fn test_qfx_transaction_from_transaction_round_trip() {
+        let qfx = QfxTransaction {
+            dt_user: Some("20251224".into()),
+            account_id: Some("1234".to_string()),
+            ..create_test_qfx_transaction()
+        };
+        let transaction: Transaction = qfx.try_into().unwrap();
+
+        let qfx = QfxTransaction::from(transaction.clone());
+        assert_eq!(qfx.name, Some("Test Payee".to_string()));
+        assert_eq!(qfx.account_id, Some("1234".to_string()));
+
+        let round_tripped: Transaction = qfx.try_into().unwrap();
+        assert_eq!(round_tripped.date, transaction.date);
+        assert_eq!(round_tripped.user_date, transaction.user_date);
+        assert_eq!(round_tripped.amount, transaction.amount);
+        assert_eq!(round_tripped.payee, transaction.payee);
+        assert_eq!(round_tripped.fitid, transaction.fitid);
+    }
+
+    #[rstest]
+    #[case("CREDIT", TransactionType::Credit)]
+    #[case("DEBIT", TransactionType::Debit)]
+    #[case("INT", TransactionType::Interest)]
+    #[case("DIV", TransactionType::Dividend)]
+    #[case("FEE", TransactionType::Fee)]
+    #[case("SRVCHG", TransactionType::ServiceCharge)]
+    #[case("DEP", TransactionType::Deposit)]
+    #[case("ATM", TransactionType::Atm)]
+    #[case("POS", TransactionType::PointOfSale)]
+    #[case("XFER", TransactionType::Transfer)]
+    #[case("CHECK", TransactionType::Check)]
+    #[case("PAYMENT", TransactionType::Payment)]
+    #[case("CASH", TransactionType::Cash)]
+    #[case("DIRECTDEP", TransactionType::DirectDeposit)]
+    #[case("DIRECTDEBIT", TransactionType::DirectDebit)]
+    #[case("REPEATPMT", TransactionType::RepeatPayment)]
+    #[case("HOLD", TransactionType::Hold)]
+    fn test_transaction_type_spec_values(#[case] code: &str, #[case] expected: TransactionType) {
+        let kind = TransactionType::from(code);
+        assert_eq!(kind.as_str(), code);
+        assert_eq!(kind, expected);
+    }
+
+    #[rstest]
+    #[case(" xfer ", TransactionType::Transfer)]
+    #[case("OTHER", TransactionType::Other("OTHER".to_string()))]
+    #[case("WIRE", TransactionType::Other("WIRE".to_string()))]
+    fn test_transaction_type_from_str(#[case] code: &str, #[case] expected: TransactionType) {
+        assert_eq!(TransactionType::from(code), expected);
+    }
+
+    #[rstest]
+    #[case("DEBIT", Some(true))]
+    #[case("ATM", Some(true))]
+    #[case("DEP", Some(false))]
+    #[case("XFER", None)]
+    #[case("WIRE", None)]
+    fn test_transaction_type_is_outflow(#[case] code: &str, #[case] expected: Option<bool>) {
+        assert_eq!(TransactionType::from(code).is_outflow(), expected);
+    }
+
+    #[test]
+    fn test_transaction_kind() {
+        let transaction = create_test_transaction((2025, 12, 26), "-50.00", None);
+        assert_eq!(transaction.kind(), TransactionType::Debit);
+    }
+
+    #[test]
+    fn test_with_overrides() {
+        let transaction = Transaction {
+            payee: Some("Coffee Shop".to_string()),
+            memo: Some("Morning coffee".to_string()),
+            ..create_test_transaction((2025, 12, 26), "-50.00", Some("202512260"))
+        };
+
+        let updated = transaction.with_overrides(TransactionOverrides {
+            payee: Some("Blue Bottle".to_string()),
+            amount: Some(Decimal::from_str("-5.00").unwrap()),
+            ..Default::default()
+        });
+        assert_eq!(updated.payee, Some("Blue Bottle".to_string()));
+        assert_eq!(updated.amount, Decimal::from_str("-5.00").unwrap());
+        assert_eq!(updated.memo, Some("Morning coffee".to_string()));
+        assert_eq!(updated.fitid, Some("202512260".to_string()));
+        assert_eq!(updated.date, NaiveDate::from_ymd_opt(2025, 12, 26).unwrap());
+    }
+
+    #[test]
+    fn test_with_overrides_default_is_noop() {
+        let transaction = create_test_transaction((2025, 12, 26), "-50.00", None);
+        let updated = transaction
+            .clone()
+            .with_overrides(TransactionOverrides::default());
+        assert_eq!(updated.sort_key(), transaction.sort_key());
+        assert_eq!(updated.payee, transaction.payee);
+    }
+
+    #[rstest]
+    #[case(TransactionFilter::default(), true)]
+    #[case(TransactionFilter { date_from: Some(ymd(2025, 12, 26)), ..Default::default() }, true)]
+    #[case(TransactionFilter { date_from: Some(ymd(2025, 12, 27)), ..Default::default() }, false)]
+    #[case(TransactionFilter { date_to: Some(ymd(2025, 12, 26)), ..Default::default() }, true)]
+    #[case(TransactionFilter { date_to: Some(ymd(2025, 12, 25)), ..Default::default() }, false)]
+    #[case(TransactionFilter { min_amount: Some(dec("-50.00")), ..Default::default() }, true)]
+    #[case(TransactionFilter { min_amount: Some(dec("-49.99")), ..Default::default() }, false)]
+    #[case(TransactionFilter { max_amount: Some(dec("-50")), ..Default::default() }, true)]
+    #[case(TransactionFilter { max_amount: Some(dec("-50.01")), ..Default::default() }, false)]
+    #[case(
+        TransactionFilter { types: vec![TransactionType::Credit, TransactionType::Debit], ..Default::default() },
+        true
+    )]
+    #[case(TransactionFilter { types: vec![TransactionType::Credit], ..Default::default() }, false)]
+    fn test_transaction_filter_matches(#[case] filter: TransactionFilter, #[case] expected: bool) {
+        let transaction = create_test_transaction((2025, 12, 26), "-50.00", None);
+        assert_eq!(filter.matches(&transaction), expected);
+    }
+
+    fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn dec(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    fn create_test_transaction(
+        date: (i32, u32, u32),
+        amount: &str,
+        fitid: Option<&str>,
+    ) -> Transaction {
+        Transaction {
+            date: NaiveDate::from_ymd_opt(date.0, date.1, date.2).unwrap(),
+            amount: Decimal::from_str(amount).unwrap(),
+            payee: None,
+            transaction_type: "DEBIT".to_string(),
+            fitid: fitid.map(str::to_string),
+            check_number: None,
+            status: None,
+            memo: None,
+            account_id: None,
+            currency: None,
+            original_currency: None,
+            original_amount: None,
+            raw_source: None,
+            user_date: None,
+            normalized_payee: None,
+        }
+    }
+
+    #[test]
+    fn test_sort_key() {
+        let transaction = create_test_transaction((2025, 12, 26), "-50.00", Some("202512260"));
+        assert_eq!(
+            transaction.sort_key(),
+            (
+                NaiveDate::from_ymd_opt(2025, 12, 26).unwrap(),
+                Decimal::from_str("-50.00").unwrap(),
+                "202512260".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_sort_key_missing_fitid_is_empty() {
+        let transaction = create_test_transaction((2025, 12, 26), "-50.00", None);
+        assert_eq!(transaction.sort_key().2, "");
+    }
+
+    #[test]
+    fn test_sort_by_sort_key_ordering() {
+        let mut transactions = [
+            create_test_transaction((2025, 12, 27), "-10.00", Some("a")),
+            create_test_transaction((2025, 12, 26), "10.00", Some("b")),
+            create_test_transaction((2025, 12, 26), "-50.00", Some("c")),
+            create_test_transaction((2025, 12, 26), "10.00", Some("a")),
+            create_test_transaction((2025, 12, 26), "10.00", None),
+        ];
+
+        transactions.sort_by_key(Transaction::sort_key);
+
+        let order: Vec<_> = transactions
+            .iter()
+            .map(|t| (t.amount.to_string(), t.fitid.clone()))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                ("-50.00".to_string(), Some("c".to_string())),
+                ("10.00".to_string(), None),
+                ("10.00".to_string(), Some("a".to_string())),
+                ("10.00".to_string(), Some("b".to_string())),
+                ("-10.00".to_string(), Some("a".to_string())),
+            ]
+        );
+    }
+
     #[test]
     fn test_transaction_serialization() {
         let transaction = Transaction {
@@ -134,8 +862,16 @@ mod tests {
             payee: Some("Test Payee".to_string()),
             transaction_type: "DEBIT".to_string(),
             fitid: Some("202512260".to_string()),
+            check_number: None,
             status: None,
             memo: Some("Test memo".to_string()),
+            account_id: None,
+            currency: None,
+            original_currency: None,
+            original_amount: None,
+            raw_source: None,
+            user_date: None,
+            normalized_payee: None,
         };
 
         let json = serde_json::to_string(&transaction).unwrap();
@@ -0,0 +1,117 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ParseWarning {
+    OfxStatus {
+        code: String,
+        message: Option<String>,
+    },
+    /// A `BANKTRANLIST` declared `TRNCOUNT` transactions but held a
+    /// different number, which usually means the download was truncated.
+    TransactionCountMismatch { declared: usize, actual: usize },
+    /// A transaction was skipped because its date could not be read, with
+    /// `strict_dates(false)`.
+    InvalidDate { raw: String },
+    /// An invalid date was accepted as `corrected`, with `strict_dates(false)`.
+    DateCorrected {
+        original: String,
+        corrected: NaiveDate,
+    },
+    /// A transaction is dated after today, as given by
+    /// `ParserBuilder::clock`, which often means a misread date format.
+    FutureDate { date: NaiveDate },
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseWarning::OfxStatus { code, message } => match message {
+                Some(message) => write!(f, "OFX status {}: {}", code, message),
+                None => write!(f, "OFX status {}", code),
+            },
+            ParseWarning::TransactionCountMismatch { declared, actual } => write!(
+                f,
+                "Statement declares {} transactions but contains {}",
+                declared, actual
+            ),
+            ParseWarning::InvalidDate { raw } => {
+                write!(f, "Skipped transaction with invalid date {:?}", raw)
+            }
+            ParseWarning::DateCorrected {
+                original,
+                corrected,
+            } => write!(f, "Invalid date {} read as {}", original, corrected),
+            ParseWarning::FutureDate { date } => {
+                write!(f, "Transaction dated {} is in the future", date)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(
+        Some("Statement may be incomplete"),
+        "OFX status 2000: Statement may be incomplete"
+    )]
+    #[case(None, "OFX status 2000")]
+    fn test_ofx_status_display(#[case] message: Option<&str>, #[case] expected: &str) {
+        let warning = ParseWarning::OfxStatus {
+            code: "2000".to_string(),
+            message: message.map(str::to_string),
+        };
+        assert_eq!(warning.to_string(), expected);
+    }
+
+    #[test]
+    fn test_date_corrected_display() {
+        let warning = ParseWarning::DateCorrected {
+            original: "20250431".to_string(),
+            corrected: NaiveDate::from_ymd_opt(2025, 4, 30).unwrap(),
+        };
+        assert_eq!(
+            warning.to_string(),
+            "Invalid date 20250431 read as 2025-04-30"
+        );
+    }
+
+    #[test]
+    fn test_transaction_count_mismatch_display() {
+        let warning = ParseWarning::TransactionCountMismatch {
+            declared: 3,
+            actual: 2,
+        };
+        assert_eq!(
+            warning.to_string(),
+            "Statement declares 3 transactions but contains 2"
+        );
+    }
+
+    #[test]
+    fn test_future_date_display() {
+        let warning = ParseWarning::FutureDate {
+            date: NaiveDate::from_ymd_opt(2099, 1, 1).unwrap(),
+        };
+        assert_eq!(
+            warning.to_string(),
+            "Transaction dated 2099-01-01 is in the future"
+        );
+    }
+
+    #[test]
+    fn test_invalid_date_display() {
+        let warning = ParseWarning::InvalidDate {
+            raw: "2025".to_string(),
+        };
+        assert_eq!(
+            warning.to_string(),
+            "Skipped transaction with invalid date \"2025\""
+        );
+    }
+}